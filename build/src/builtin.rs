@@ -0,0 +1,704 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    fs,
+    path::Path,
+};
+
+use anyhow::{bail, Result};
+use snapper_core::keccak256;
+use snapper_solc::output::{ABIInfo, Component, Value};
+
+use crate::{constants, Contract, Generator};
+
+/// Bindings without third party crates, encoded by `snapper::abi`.
+///
+/// Each contract is a module with `<name>Call` and `<name>Return` of
+/// functions, a struct for each event and custom error, `constructorCall`,
+/// and structs of tuples.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BuiltinGenerator;
+
+impl Generator for BuiltinGenerator {
+    fn generate(&self, contract: &Contract, target: &Path) -> Result<()> {
+        fs::write(target, self.contract_code(contract)?)?;
+        Ok(())
+    }
+}
+
+impl BuiltinGenerator {
+    /// Rust code of contract `name` with `abi`.
+    pub fn code(&self, name: &str, abi: &[ABIInfo]) -> Result<String> {
+        self.module_code(name, abi, Vec::new())
+    }
+
+    /// Rust code of `contract`, with its `constants`.
+    pub fn contract_code(&self, contract: &Contract) -> Result<String> {
+        let abi: Vec<ABIInfo> = serde_json::from_slice(&fs::read(contract.abi_path())?)?;
+        self.module_code(&contract.name, &abi, vec![constants(contract)?])
+    }
+
+    fn module_code(&self, name: &str, abi: &[ABIInfo], items: Vec<String>) -> Result<String> {
+        let mut module = Module::default();
+
+        let mut overloads = BTreeMap::<&str, usize>::new();
+        for item in abi {
+            match item {
+                ABIInfo::Function { name, .. } => *overloads.entry(name).or_default() += 1,
+                ABIInfo::Event { name, .. } | ABIInfo::Error { name, .. } => {
+                    module.reserved.insert(name.clone());
+                }
+                _ => {}
+            }
+        }
+
+        let mut index = BTreeMap::<&str, usize>::new();
+
+        for item in abi {
+            match item {
+                ABIInfo::Function {
+                    name,
+                    inputs,
+                    outputs,
+                    ..
+                } => {
+                    let ident = if overloads[name.as_str()] > 1 {
+                        let i = index.entry(name).or_default();
+                        *i += 1;
+                        format!("{name}_{}", *i - 1)
+                    } else {
+                        name.clone()
+                    };
+
+                    module.reserved.insert(format!("{ident}Call"));
+                    module.reserved.insert(format!("{ident}Return"));
+                    module.function(name, &ident, inputs, outputs)?;
+                }
+                ABIInfo::Event {
+                    name,
+                    inputs,
+                    anonymous,
+                } => module.event(name, inputs, *anonymous)?,
+                ABIInfo::Error { name, inputs } => module.error(name, inputs)?,
+                ABIInfo::Constructor { inputs, .. } => module.constructor(inputs)?,
+                ABIInfo::Receive { .. } | ABIInfo::Fallback { .. } => {}
+            }
+        }
+
+        let mut res = String::new();
+
+        writeln!(res, "/// Bindings of `{name}`.")?;
+        writeln!(
+            res,
+            "#[allow(non_camel_case_types, non_snake_case, unused_mut, unused_variables, clippy::all)]"
+        )?;
+        writeln!(res, "pub mod {name} {{")?;
+        writeln!(res, "    use ::snapper::abi::{{self, AbiType}};")?;
+
+        module.items.extend(items);
+
+        for code in module.structs.values().chain(module.items.iter()) {
+            res.push('\n');
+            for line in code.lines() {
+                if line.is_empty() {
+                    res.push('\n');
+                } else {
+                    writeln!(res, "    {line}")?;
+                }
+            }
+        }
+
+        res.push_str("}\n");
+
+        Ok(res)
+    }
+}
+
+/// Parameter of function, event, error or tuple.
+#[derive(Clone)]
+pub(crate) struct Param {
+    name: String,
+    ty: String,
+    internal_type: Option<String>,
+    components: Vec<Param>,
+    indexed: bool,
+}
+
+impl From<&Value> for Param {
+    fn from(v: &Value) -> Self {
+        Self {
+            name: v.name.clone(),
+            ty: v.ty.clone(),
+            internal_type: v.internal_type.clone(),
+            components: v.components.iter().map(Param::from).collect(),
+            indexed: v.indexed,
+        }
+    }
+}
+
+impl From<&Component> for Param {
+    fn from(c: &Component) -> Self {
+        Self {
+            name: c.name.clone(),
+            ty: c.ty.clone(),
+            internal_type: c.internal_type.clone(),
+            components: c.components.iter().map(Param::from).collect(),
+            indexed: false,
+        }
+    }
+}
+
+impl Param {
+    /// Type in signature, tuples are written as `(t1,t2)`.
+    fn canonical(&self) -> String {
+        match self.ty.strip_prefix("tuple") {
+            Some(suffix) => {
+                let fields: Vec<_> = self.components.iter().map(Param::canonical).collect();
+                format!("({}){suffix}", fields.join(","))
+            }
+            None => self.ty.clone(),
+        }
+    }
+
+    /// Element of array type.
+    fn element(&self) -> Option<(Param, Option<&str>)> {
+        let inner = self.ty.strip_suffix(']')?;
+        let pos = inner.rfind('[')?;
+
+        let internal_type = self
+            .internal_type
+            .as_ref()
+            .and_then(|t| Some(t.strip_suffix(']')?[..t.rfind('[')?].to_string()));
+
+        let element = Param {
+            name: self.name.clone(),
+            ty: inner[..pos].to_string(),
+            internal_type,
+            components: self.components.clone(),
+            indexed: false,
+        };
+
+        let len = &inner[pos + 1..];
+        Some((element, (!len.is_empty()).then_some(len)))
+    }
+
+    /// Indexed event field stored as hash.
+    fn is_hashed(&self) -> bool {
+        self.indexed
+            && (matches!(self.ty.as_str(), "string" | "bytes")
+                || self.ty.ends_with(']')
+                || self.ty.starts_with("tuple"))
+    }
+}
+
+pub(crate) const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "do", "dyn", "else", "enum",
+    "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro", "match",
+    "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static", "struct", "trait",
+    "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while",
+    "yield",
+];
+
+/// Rust identifier of `name`, keywords are raw identifiers. `crate`,
+/// `self`, `super` and `Self` can't be raw, they are suffixed by `_`.
+pub(crate) fn ident(name: &str) -> String {
+    if matches!(name, "crate" | "self" | "super" | "Self") {
+        format!("{name}_")
+    } else if KEYWORDS.contains(&name) {
+        format!("r#{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+/// Field name of `i`th parameter `name`.
+fn field_name(name: &str, i: usize) -> String {
+    if name.is_empty() {
+        format!("_{i}")
+    } else {
+        ident(name)
+    }
+}
+
+pub(crate) fn bytes_literal(bytes: &[u8]) -> String {
+    let bytes: Vec<_> = bytes.iter().map(|b| format!("0x{b:02x}")).collect();
+    format!("[{}]", bytes.join(", "))
+}
+
+pub(crate) fn signature(name: &str, params: &[Param]) -> String {
+    let types: Vec<_> = params.iter().map(Param::canonical).collect();
+    format!("{name}({})", types.join(","))
+}
+
+#[derive(Default)]
+struct Module {
+    /// Structs of tuples keyed by name.
+    structs: BTreeMap<String, String>,
+    /// Names of structs by solidity struct, like `Lock.Info`.
+    struct_names: BTreeMap<String, String>,
+    /// Names of events, errors, calls and returns.
+    reserved: BTreeSet<String>,
+    items: Vec<String>,
+}
+
+impl Module {
+    /// Rust type of `param`, structs of tuples are generated as needed.
+    fn rust_type(&mut self, param: &Param) -> Result<String> {
+        if let Some((element, len)) = param.element() {
+            let element = self.rust_type(&element)?;
+
+            return Ok(match len {
+                Some(len) => format!("[{element}; {len}]"),
+                None => format!("Vec<{element}>"),
+            });
+        }
+
+        let ty = param.ty.as_str();
+
+        let bits = |prefix: &str| -> Result<usize> {
+            let bits = &ty[prefix.len()..];
+            if bits.is_empty() {
+                Ok(256)
+            } else {
+                Ok(bits.parse()?)
+            }
+        };
+
+        let res = match ty {
+            "address" => "abi::Address".to_string(),
+            "bool" => "bool".to_string(),
+            "string" => "String".to_string(),
+            "bytes" => "abi::Bytes".to_string(),
+            "function" => "abi::FixedBytes<24>".to_string(),
+            "tuple" => self.tuple(param)?,
+            _ if ty.starts_with("bytes") => format!("abi::FixedBytes<{}>", &ty[5..]),
+            _ if ty.starts_with("uint") => match bits("uint")? {
+                0..=8 => "u8",
+                9..=16 => "u16",
+                17..=32 => "u32",
+                33..=64 => "u64",
+                65..=128 => "u128",
+                _ => "abi::U256",
+            }
+            .to_string(),
+            _ if ty.starts_with("int") => match bits("int")? {
+                0..=8 => "i8",
+                9..=16 => "i16",
+                17..=32 => "i32",
+                33..=64 => "i64",
+                65..=128 => "i128",
+                _ => "abi::I256",
+            }
+            .to_string(),
+            _ => bail!("Unsupported ABI type `{ty}`"),
+        };
+
+        Ok(res)
+    }
+
+    /// Struct of tuple `param`, named by its solidity struct.
+    ///
+    /// Structs of other contracts or libraries whose name is already taken
+    /// are prefixed by it, like `Lib_Info` for `Lib.Info`.
+    fn tuple(&mut self, param: &Param) -> Result<String> {
+        let Some(path) = param
+            .internal_type
+            .as_deref()
+            .and_then(|t| t.strip_prefix("struct "))
+        else {
+            let name = format!("Tuple{}", self.structs.len());
+            return self.tuple_named(name, param);
+        };
+
+        if let Some(name) = self.struct_names.get(path) {
+            return Ok(name.clone());
+        }
+
+        let taken = |name: &str| self.structs.contains_key(name) || self.reserved.contains(name);
+
+        let mut name = path.rsplit('.').next().unwrap_or(path).to_string();
+        if taken(&name) {
+            name = path.replace('.', "_");
+        }
+
+        if taken(&name) {
+            bail!("Name of struct `{path}` conflicts with `{name}`");
+        }
+
+        self.struct_names.insert(path.to_string(), name.clone());
+        self.tuple_named(name, param)
+    }
+
+    fn tuple_named(&mut self, name: String, param: &Param) -> Result<String> {
+        if !self.structs.contains_key(&name) {
+            // Reserve name for recursive types.
+            self.structs.insert(name.clone(), String::new());
+
+            let code = self.tuple_struct(&name, &param.components, "")?;
+            self.structs.insert(name.clone(), code);
+        }
+
+        Ok(name)
+    }
+
+    /// Fields of `params` as `(name, type)`.
+    fn fields(&mut self, params: &[Param]) -> Result<Vec<(String, String)>> {
+        params
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let ty = if p.is_hashed() {
+                    "abi::FixedBytes<32>".to_string()
+                } else {
+                    self.rust_type(p)?
+                };
+
+                Ok((field_name(&p.name, i), ty))
+            })
+            .collect()
+    }
+
+    fn plain_struct(doc: &str, name: &str, fields: &[(String, String)]) -> String {
+        let mut res = String::new();
+
+        if !doc.is_empty() {
+            res.push_str(&format!("/// {doc}\n"));
+        }
+
+        res.push_str("#[derive(Debug, Clone, PartialEq)]\n");
+
+        if fields.is_empty() {
+            res.push_str(&format!("pub struct {name} {{}}\n"));
+            return res;
+        }
+
+        res.push_str(&format!("pub struct {name} {{\n"));
+        for (field, ty) in fields {
+            res.push_str(&format!("    pub {field}: {ty},\n"));
+        }
+        res.push_str("}\n");
+
+        res
+    }
+
+    /// Struct implementing `AbiType` as tuple of `params`.
+    fn tuple_struct(&mut self, name: &str, params: &[Param], doc: &str) -> Result<String> {
+        let fields = self.fields(params)?;
+
+        let mut res = Self::plain_struct(doc, name, &fields);
+
+        let types: Vec<_> = fields
+            .iter()
+            .map(|(_, ty)| format!("<{ty} as AbiType>::param_type()"))
+            .collect();
+        let tokens: Vec<_> = fields
+            .iter()
+            .map(|(field, _)| format!("self.{field}.to_token()"))
+            .collect();
+        let values: Vec<_> = fields
+            .iter()
+            .map(|(field, _)| format!("{field}: abi::next(&mut tokens)?"))
+            .collect();
+
+        write!(
+            res,
+            "
+impl AbiType for {name} {{
+    fn param_type() -> abi::ParamType {{
+        abi::ParamType::Tuple(vec![{}])
+    }}
+
+    fn to_token(&self) -> abi::Token {{
+        abi::Token::Tuple(vec![{}])
+    }}
+
+    fn from_token(token: abi::Token) -> abi::Result<Self> {{
+        let abi::Token::Tuple(tokens) = token else {{
+            return Err(abi::Error::InvalidAbiData);
+        }};
+
+        let mut tokens = tokens.into_iter();
+
+        Ok(Self {{ {} }})
+    }}
+}}
+",
+            types.join(", "),
+            tokens.join(", "),
+            values.join(", "),
+        )?;
+
+        Ok(res)
+    }
+
+    fn function(
+        &mut self,
+        name: &str,
+        ident: &str,
+        inputs: &[Value],
+        outputs: &[Value],
+    ) -> Result<()> {
+        let inputs: Vec<Param> = inputs.iter().map(Param::from).collect();
+        let outputs: Vec<Param> = outputs.iter().map(Param::from).collect();
+
+        let signature = signature(name, &inputs);
+        let selector = &keccak256(signature.as_bytes())[..4];
+
+        let call = format!("{ident}Call");
+        let ret = format!("{ident}Return");
+
+        let mut code = self.tuple_struct(&call, &inputs, &format!("Call `{signature}`."))?;
+
+        write!(
+            code,
+            "
+impl {call} {{
+    pub const SIGNATURE: &'static str = {signature:?};
+    pub const SELECTOR: [u8; 4] = {};
+
+    /// Calldata with selector.
+    pub fn encode(&self) -> Vec<u8> {{
+        abi::encode_call(&Self::SELECTOR, self)
+    }}
+
+    pub fn decode(data: &[u8]) -> abi::Result<Self> {{
+        abi::decode_call(&Self::SELECTOR, data)
+    }}
+
+    pub fn decode_returns(data: &[u8]) -> abi::Result<{ret}> {{
+        abi::decode_params(data)
+    }}
+}}
+",
+            bytes_literal(selector),
+        )?;
+
+        self.items.push(code);
+
+        let code = self.tuple_struct(&ret, &outputs, &format!("Return of `{signature}`."))?;
+        self.items.push(code);
+
+        Ok(())
+    }
+
+    fn error(&mut self, name: &str, inputs: &[Value]) -> Result<()> {
+        let inputs: Vec<Param> = inputs.iter().map(Param::from).collect();
+
+        let signature = signature(name, &inputs);
+        let selector = &keccak256(signature.as_bytes())[..4];
+
+        let mut code = self.tuple_struct(name, &inputs, &format!("Error `{signature}`."))?;
+
+        write!(
+            code,
+            "
+impl {name} {{
+    pub const SIGNATURE: &'static str = {signature:?};
+    pub const SELECTOR: [u8; 4] = {};
+
+    /// Revert data with selector.
+    pub fn encode(&self) -> Vec<u8> {{
+        abi::encode_call(&Self::SELECTOR, self)
+    }}
+
+    pub fn decode(data: &[u8]) -> abi::Result<Self> {{
+        abi::decode_call(&Self::SELECTOR, data)
+    }}
+}}
+",
+            bytes_literal(selector),
+        )?;
+
+        self.items.push(code);
+
+        Ok(())
+    }
+
+    fn event(&mut self, name: &str, inputs: &[Value], anonymous: bool) -> Result<()> {
+        let inputs: Vec<Param> = inputs.iter().map(Param::from).collect();
+
+        let signature = signature(name, &inputs);
+        let topic = keccak256(signature.as_bytes());
+
+        let fields = self.fields(&inputs)?;
+        let mut code = Self::plain_struct(&format!("Event `{signature}`."), name, &fields);
+
+        let data_types: Vec<_> = inputs
+            .iter()
+            .zip(&fields)
+            .filter(|(p, _)| !p.indexed)
+            .map(|(_, (_, ty))| format!("<{ty} as AbiType>::param_type()"))
+            .collect();
+
+        let values: Vec<_> = inputs
+            .iter()
+            .zip(&fields)
+            .map(|(p, (field, _))| {
+                if p.indexed {
+                    format!("{field}: abi::topic(topics.next())?")
+                } else {
+                    format!("{field}: abi::next(&mut data)?")
+                }
+            })
+            .collect();
+
+        let check = if anonymous {
+            String::new()
+        } else {
+            "
+        if topics.next() != Some(&Self::TOPIC) {
+            return Err(abi::Error::InvalidAbiData);
+        }
+"
+            .to_string()
+        };
+
+        write!(
+            code,
+            "
+impl {name} {{
+    pub const SIGNATURE: &'static str = {signature:?};
+    /// First topic of log, unless event is anonymous.
+    pub const TOPIC: [u8; 32] = {};
+
+    pub fn decode_log(topics: &[[u8; 32]], data: &[u8]) -> abi::Result<Self> {{
+        let mut topics = topics.iter();
+{check}
+        let mut data = abi::decode(&[{}], data)?.into_iter();
+
+        Ok(Self {{ {} }})
+    }}
+}}
+",
+            bytes_literal(&topic),
+            data_types.join(", "),
+            values.join(", "),
+        )?;
+
+        self.items.push(code);
+
+        Ok(())
+    }
+
+    fn constructor(&mut self, inputs: &[Value]) -> Result<()> {
+        let inputs: Vec<Param> = inputs.iter().map(Param::from).collect();
+
+        let mut code = self.tuple_struct("constructorCall", &inputs, "Constructor arguments.")?;
+
+        code.push_str(
+            "
+impl constructorCall {
+    /// Deploy code, `bytecode` followed by arguments.
+    pub fn deploy_code(&self, bytecode: &[u8]) -> Vec<u8> {
+        let mut res = bytecode.to_vec();
+        res.extend_from_slice(&abi::encode_params(self));
+        res
+    }
+}
+",
+        );
+
+        self.items.push(code);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use snapper_core::abi::{self, ParamType, Token, U256};
+    use snapper_solc::output::ABIInfo;
+
+    use super::BuiltinGenerator;
+
+    #[test]
+    fn test_abi() {
+        assert_eq!(
+            abi::selector("transfer(address,uint256)"),
+            [0xa9, 0x05, 0x9c, 0xbb]
+        );
+
+        // `f(uint256,uint32[],bytes10,bytes)` example of solidity docs.
+        let tokens = vec![
+            Token::Uint(U256::from(0x123).0),
+            Token::Array(vec![
+                Token::Uint(U256::from(0x456).0),
+                Token::Uint(U256::from(0x789).0),
+            ]),
+            Token::FixedBytes(b"1234567890".to_vec()),
+            Token::Bytes(b"Hello, world!".to_vec()),
+        ];
+
+        let data = abi::encode(&tokens);
+        let words: Vec<_> = data.chunks(32).map(snapper_core::hex::encode).collect();
+
+        assert_eq!(words.len(), 9);
+        assert!(words[0].ends_with("0123"));
+        assert!(words[1].ends_with("0080"));
+        assert!(words[2].starts_with("31323334353637383930"));
+        assert!(words[3].ends_with("00e0"));
+        assert!(words[4].ends_with("0002"));
+        assert!(words[7].ends_with("000d"));
+        assert!(words[8].starts_with("48656c6c6f2c20776f726c6421"));
+
+        let types = [
+            ParamType::Uint(256),
+            ParamType::Array(Box::new(ParamType::Uint(32))),
+            ParamType::FixedBytes(10),
+            ParamType::Bytes,
+        ];
+        assert_eq!(abi::decode(&types, &data).unwrap(), tokens);
+        assert!(abi::decode(&types, &data[..data.len() - 32]).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let abi: Vec<ABIInfo> = serde_json::from_str(include_str!("abi.json")).unwrap();
+        let code = BuiltinGenerator.code("Lock", &abi).unwrap();
+
+        assert!(code.contains("pub mod Lock {"));
+        assert!(code.contains("pub struct constructorCall {"));
+        assert!(code.contains("pub struct withdraw_0Call {}"));
+        assert!(code.contains("pub struct withdraw_1Call {"));
+        assert!(code.contains("pub r#type: i32,"));
+        assert!(code.contains("pub _0: Vec<Info>,"));
+        assert!(code.contains("pub names: [String; 2],"));
+        assert!(code.contains("pub inner: Inner,"));
+        assert!(code.contains("SIGNATURE: &'static str = \"info(int24)\""));
+        assert!(code.contains("SIGNATURE: &'static str = \"Unauthorized(address,string)\""));
+        // Indexed string is stored as hash.
+        assert!(code.contains("pub memo: abi::FixedBytes<32>,"));
+        // Selector of `owner()`.
+        assert!(code.contains("SELECTOR: [u8; 4] = [0x8d, 0xa5, 0xcb, 0x5b]"));
+    }
+
+    #[test]
+    fn test_struct_names() {
+        let abi = r#"[
+            {"type":"function","name":"get","stateMutability":"view",
+             "inputs":[
+                {"name":"a","type":"tuple","internalType":"struct A.Info",
+                 "components":[{"name":"x","type":"uint256","internalType":"uint256"}]},
+                {"name":"b","type":"tuple","internalType":"struct B.Info",
+                 "components":[{"name":"y","type":"bool","internalType":"bool"}]},
+                {"name":"c","type":"tuple","internalType":"struct A.Moved",
+                 "components":[{"name":"z","type":"address","internalType":"address"}]}
+             ],
+             "outputs":[]},
+            {"type":"event","name":"Moved","anonymous":false,"inputs":[]}
+        ]"#;
+
+        let parsed: Vec<ABIInfo> = serde_json::from_str(abi).unwrap();
+        let code = BuiltinGenerator.code("A", &parsed).unwrap();
+
+        assert!(code.contains("pub struct Info {\n        pub x: abi::U256,"));
+        assert!(code.contains("pub struct B_Info {\n        pub y: bool,"));
+        assert!(code.contains("pub struct A_Moved {\n        pub z: abi::Address,"));
+        assert!(code.contains("pub a: Info,\n        pub b: B_Info,\n        pub c: A_Moved,"));
+
+        // File level `Info` has no prefix to tell it from `A.Info`.
+        let abi = abi.replace("struct B.Info", "struct Info");
+        let abi: Vec<ABIInfo> = serde_json::from_str(&abi).unwrap();
+        assert!(BuiltinGenerator.code("A", &abi).is_err());
+    }
+}
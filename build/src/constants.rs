@@ -0,0 +1,188 @@
+use std::{collections::BTreeMap, fmt::Write, fs};
+
+use anyhow::Result;
+use snapper_core::keccak256;
+use snapper_solc::output::ABIInfo;
+
+use crate::{
+    builtin::{bytes_literal, signature, Param},
+    modules::snake_case,
+    Contract,
+};
+
+/// Constants of `contract` for its binding module, so binaries don't read
+/// artifacts at runtime. Generators embed them, like `Lock::BYTECODE`.
+///
+/// - `BYTECODE` and `DEPLOYED_BYTECODE`, empty if not selected in
+///   `[solidity.outputs]` or unlinked.
+/// - `ABI_JSON` and `METADATA`, metadata is empty if not selected.
+/// - `<FUNCTION>_SELECTOR` of each function, overloaded functions are
+///   suffixed by index like `<FUNCTION>_0_SELECTOR`.
+pub fn constants(contract: &Contract) -> Result<String> {
+    Ok(format!(
+        "{}\n{}",
+        bytecode_constants(contract)?,
+        abi_constants(contract)?
+    ))
+}
+
+/// `BYTECODE` and `DEPLOYED_BYTECODE` of `constants`.
+fn bytecode_constants(contract: &Contract) -> Result<String> {
+    let name = &contract.name;
+    let mut res = String::new();
+
+    writeln!(
+        res,
+        "/// Bytecode of `{name}`, empty if not selected or unlinked."
+    )?;
+    writeln!(
+        res,
+        "pub const BYTECODE: &[u8] = {};",
+        byte_string(&contract.bytecode().unwrap_or_default())
+    )?;
+
+    writeln!(res)?;
+    writeln!(
+        res,
+        "/// Deployed bytecode of `{name}`, empty if not selected or unlinked."
+    )?;
+    writeln!(
+        res,
+        "pub const DEPLOYED_BYTECODE: &[u8] = {};",
+        byte_string(&contract.deployed_bytecode().unwrap_or_default())
+    )?;
+
+    Ok(res)
+}
+
+/// `constants` other than bytecode, for generators with bytecode of their
+/// own.
+pub(crate) fn abi_constants(contract: &Contract) -> Result<String> {
+    let name = &contract.name;
+
+    let abi_json = fs::read_to_string(contract.abi_path())?;
+    let abi: Vec<ABIInfo> = serde_json::from_str(&abi_json)?;
+    let metadata = fs::read_to_string(contract.artifact_path("metadata.json")).unwrap_or_default();
+
+    let mut res = String::new();
+
+    writeln!(res, "/// Abi of `{name}` as json.")?;
+    writeln!(res, "pub const ABI_JSON: &str = {abi_json:?};")?;
+
+    writeln!(res)?;
+    writeln!(
+        res,
+        "/// Metadata of `{name}` as json, empty if not selected."
+    )?;
+    writeln!(res, "pub const METADATA: &str = {metadata:?};")?;
+
+    let mut overloads = BTreeMap::<&str, usize>::new();
+    for item in &abi {
+        if let ABIInfo::Function { name, .. } = item {
+            *overloads.entry(name).or_default() += 1;
+        }
+    }
+
+    let mut index = BTreeMap::<&str, usize>::new();
+
+    for item in &abi {
+        let ABIInfo::Function { name, inputs, .. } = item else {
+            continue;
+        };
+
+        let mut ident = snake_case(name).to_uppercase();
+        if overloads[name.as_str()] > 1 {
+            let i = index.entry(name).or_default();
+            write!(ident, "_{i}")?;
+            *i += 1;
+        }
+
+        let inputs: Vec<_> = inputs.iter().map(Param::from).collect();
+        let signature = signature(name, &inputs);
+        let selector = &keccak256(signature.as_bytes())[..4];
+
+        writeln!(res)?;
+        writeln!(res, "/// Selector of `{signature}`.")?;
+        writeln!(
+            res,
+            "pub const {ident}_SELECTOR: [u8; 4] = {};",
+            bytes_literal(selector)
+        )?;
+    }
+
+    Ok(res)
+}
+
+/// Byte string literal of `bytes`, like `b"\x60\x80"`.
+fn byte_string(bytes: &[u8]) -> String {
+    let mut res = String::from("b\"");
+    for b in bytes {
+        res.push_str(&format!("\\x{b:02x}"));
+    }
+    res.push('"');
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::constants;
+    use crate::{BuiltinGenerator, Contract};
+
+    #[test]
+    fn test_constants() {
+        let dir = std::env::temp_dir().join("snapper-constants-test");
+        fs::create_dir_all(&dir).unwrap();
+
+        let contract = Contract {
+            name: "Lock".to_string(),
+            source: "Lock.sol".to_string(),
+            artifact_dir: dir.clone(),
+        };
+
+        fs::write(contract.abi_path(), include_str!("abi.json")).unwrap();
+        fs::write(contract.artifact_path("bytecode"), [0x60, 0x80]).unwrap();
+        let _ = fs::remove_file(contract.artifact_path("deployed.bytecode"));
+        let _ = fs::remove_file(contract.artifact_path("metadata.json"));
+
+        let code = constants(&contract).unwrap();
+
+        assert!(code.contains(r#"pub const BYTECODE: &[u8] = b"\x60\x80";"#));
+        assert!(code.contains(r#"pub const DEPLOYED_BYTECODE: &[u8] = b"";"#));
+        assert!(code.contains(r#"pub const METADATA: &str = "";"#));
+        assert!(code.contains("pub const ABI_JSON: &str = \"["));
+        assert!(code.contains("pub const OWNER_SELECTOR: [u8; 4]"));
+        assert!(code.contains("/// Selector of `withdraw(uint256)`."));
+        assert!(code.contains("pub const WITHDRAW_0_SELECTOR: [u8; 4] = [0x3c, 0xcf, 0xd6, 0x0b];"));
+        assert!(code.contains("pub const WITHDRAW_1_SELECTOR: [u8; 4] = [0x2e, 0x1a, 0x7d, 0x4d];"));
+
+        // Inside module of each generator.
+        let code = BuiltinGenerator.contract_code(&contract).unwrap();
+        assert!(code.contains("pub mod Lock {"));
+        assert!(code.contains("\n    pub const BYTECODE: &[u8] = b\"\\x60\\x80\";\n"));
+
+        #[cfg(feature = "eth-ethers")]
+        {
+            let code = crate::EthersGenerator.code(&contract).unwrap();
+            let module = &code[code.find("pub mod lock {").unwrap()..];
+            assert!(module.contains(r#"pub const BYTECODE: &[u8] = b"\x60\x80";"#));
+            assert!(module.contains("pub const WITHDRAW_1_SELECTOR: [u8; 4]"));
+        }
+
+        // Bytecode of `sol!` isn't shadowed.
+        #[cfg(feature = "eth-alloy")]
+        {
+            let code = crate::AlloyGenerator.code(&contract).unwrap();
+            assert!(code.contains("pub mod Lock {"));
+            assert!(code.contains("#[sol(rpc, bytecode = \"6080\")]"));
+            assert!(code.contains("pub use self::Lock::*;"));
+            assert!(!code.contains("BYTECODE: &[u8]"));
+            assert!(!code.contains("pub static BYTECODE"));
+            assert!(code.contains(
+                "\n    pub static DEPLOYED_BYTECODE: alloy::primitives::Bytes = alloy::primitives::Bytes::new();\n"
+            ));
+            assert!(code.contains("\n    pub const WITHDRAW_1_SELECTOR: [u8; 4]"));
+        }
+    }
+}
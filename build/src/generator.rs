@@ -0,0 +1,213 @@
+use std::{
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+
+/// Artifacts of compiled contract, input of binding generators.
+#[derive(Debug, Clone)]
+pub struct Contract {
+    /// Contract name, like `Lock`.
+    pub name: String,
+    /// Source unit name, like `Lock.sol`.
+    pub source: String,
+    /// Artifact directory of source unit.
+    pub artifact_dir: PathBuf,
+}
+
+impl Contract {
+    pub fn abi_path(&self) -> PathBuf {
+        self.artifact_path("abi.json")
+    }
+
+    /// Path of artifact with `suffix`, like `bytecode`.
+    pub fn artifact_path(&self, suffix: &str) -> PathBuf {
+        self.artifact_dir.join(format!("{}.{suffix}", self.name))
+    }
+
+    /// Linked bytecode, `None` if not selected or unlinked.
+    pub fn bytecode(&self) -> Option<Vec<u8>> {
+        fs::read(self.artifact_path("bytecode")).ok()
+    }
+
+    /// Linked deployed bytecode, `None` if not selected or unlinked.
+    pub fn deployed_bytecode(&self) -> Option<Vec<u8>> {
+        fs::read(self.artifact_path("deployed.bytecode")).ok()
+    }
+
+    /// Artifacts read by generators and `constants`, bindings are
+    /// regenerated if any changed.
+    pub fn inputs(&self) -> Vec<PathBuf> {
+        ["abi.json", "bytecode", "deployed.bytecode", "metadata.json"]
+            .into_iter()
+            .map(|s| self.artifact_path(s))
+            .filter(|p| p.exists())
+            .collect()
+    }
+}
+
+/// Binding generator, selected by `eth-*` features or `Builder::generator`.
+pub trait Generator: Debug {
+    /// Write rust bindings of `contract` into `target`, with its `constants`
+    /// inside the module of contract.
+    fn generate(&self, contract: &Contract, target: &Path) -> Result<()>;
+}
+
+/// Generator of enabled feature, `eth-alloy` is preferred over `eth-ethers`.
+/// `BuiltinGenerator` is used without them.
+pub fn default_generator() -> Box<dyn Generator> {
+    #[cfg(feature = "eth-alloy")]
+    return Box::new(AlloyGenerator);
+
+    #[cfg(all(feature = "eth-ethers", not(feature = "eth-alloy")))]
+    return Box::new(EthersGenerator);
+
+    #[cfg(not(any(feature = "eth-ethers", feature = "eth-alloy")))]
+    return Box::new(crate::BuiltinGenerator);
+}
+
+/// Bindings by `ethers_contract_abigen::Abigen`.
+#[cfg(feature = "eth-ethers")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EthersGenerator;
+
+#[cfg(feature = "eth-ethers")]
+impl EthersGenerator {
+    /// Rust code of `contract`, with its `constants` in module like `lock`.
+    pub fn code(&self, contract: &Contract) -> Result<String> {
+        use anyhow::anyhow;
+        use ethers_contract_abigen::{Abigen, ContractBindings};
+
+        let abi_path = contract.abi_path();
+
+        let (mut expanded, _) = Abigen::new(
+            &contract.name,
+            abi_path.to_str().ok_or(anyhow!("Failed to get path"))?,
+        )
+        .map_err(|e| anyhow!("New Failed: {e}"))?
+        .expand()
+        .map_err(|e| anyhow!("Generate Failed: {e}"))?;
+
+        let constants = crate::constants(contract)?;
+        expanded.abi_structs = format!("{}\n{constants}", expanded.abi_structs)
+            .parse()
+            .map_err(|e| anyhow!("Generate Failed: {e}"))?;
+
+        // `pub use lock::*` of contracts in the same source file all export
+        // constants.
+        let tokens = format!(
+            "#[allow(ambiguous_glob_reexports)]\n{}",
+            expanded.into_tokens()
+        )
+        .parse()
+        .map_err(|e| anyhow!("Generate Failed: {e}"))?;
+
+        let bindings = ContractBindings {
+            name: contract.name.clone(),
+            tokens,
+            format: true,
+        };
+
+        Ok(bindings.to_string())
+    }
+}
+
+#[cfg(feature = "eth-ethers")]
+impl Generator for EthersGenerator {
+    fn generate(&self, contract: &Contract, target: &Path) -> Result<()> {
+        fs::write(target, self.code(contract)?)?;
+        Ok(())
+    }
+}
+
+/// Bindings by `alloy::sol!`, with call and return structs, events, errors,
+/// and `deploy` when bytecode is linked.
+///
+/// Crate including bindings depends on `alloy` with `sol-types`, `json` and
+/// `contract` features.
+#[cfg(feature = "eth-alloy")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AlloyGenerator;
+
+#[cfg(feature = "eth-alloy")]
+impl AlloyGenerator {
+    /// Rust code of `contract`, bindings of `sol!` are re-exported by module
+    /// of contract with its `constants`.
+    ///
+    /// `BYTECODE` and `DEPLOYED_BYTECODE` are the `alloy::primitives::Bytes`
+    /// statics of `sol!`, empty if not selected or unlinked.
+    pub fn code(&self, contract: &Contract) -> Result<String> {
+        use std::fmt::Write;
+
+        use snapper_core::hex;
+
+        let name = &contract.name;
+
+        let mut attrs = vec!["rpc".to_string()];
+        let mut statics = String::new();
+
+        for (ident, attr, doc, bytecode) in [
+            ("BYTECODE", "bytecode", "Bytecode", contract.bytecode()),
+            (
+                "DEPLOYED_BYTECODE",
+                "deployed_bytecode",
+                "Deployed bytecode",
+                contract.deployed_bytecode(),
+            ),
+        ] {
+            if let Some(code) = bytecode {
+                attrs.push(format!("{attr} = \"{}\"", hex::encode(&code)));
+            } else {
+                // Defined by `sol!` otherwise.
+                writeln!(
+                    statics,
+                    "/// {doc} of `{name}`, empty if not selected or unlinked."
+                )?;
+                writeln!(
+                    statics,
+                    "pub static {ident}: alloy::primitives::Bytes = alloy::primitives::Bytes::new();\n"
+                )?;
+            }
+        }
+
+        let mut res = String::new();
+
+        writeln!(res, "/// Bindings of `{name}`.")?;
+        writeln!(res, "#[allow(non_snake_case)]")?;
+        writeln!(res, "pub mod {name} {{")?;
+        write!(
+            res,
+            "    alloy::sol!(\n        \
+                 #[allow(missing_docs, clippy::all)]\n        \
+                 #[sol({})]\n        \
+                 {name},\n        \
+                 {:?}\n    \
+             );\n\n    \
+             pub use self::{name}::*;\n\n",
+            attrs.join(", "),
+            contract.abi_path().display().to_string(),
+        )?;
+
+        for line in (statics + &crate::constants::abi_constants(contract)?).lines() {
+            if line.is_empty() {
+                res.push('\n');
+            } else {
+                writeln!(res, "    {line}")?;
+            }
+        }
+
+        res.push_str("}\n");
+
+        Ok(res)
+    }
+}
+
+#[cfg(feature = "eth-alloy")]
+impl Generator for AlloyGenerator {
+    fn generate(&self, contract: &Contract, target: &Path) -> Result<()> {
+        fs::write(target, self.code(contract)?)?;
+        Ok(())
+    }
+}
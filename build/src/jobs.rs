@@ -0,0 +1,218 @@
+use std::{collections::BTreeSet, path::Path};
+
+use anyhow::{bail, Result};
+use semver::Version;
+use snapper_core::{Compiler, SnapperFile};
+use snapper_solc::{pragma, utils, CompilerVersions, Sources};
+
+/// Sources compiled by one compiler configuration.
+pub struct Job {
+    pub name: String,
+    pub snapper: SnapperFile,
+    pub roots: BTreeSet<String>,
+}
+
+/// Partition sources into jobs by compiler configuration.
+///
+/// Source units listed in `[solidity.overrides]` use their own configuration,
+/// others use the first compiler compatible with pragmas of it and its imports.
+///
+/// With `solidity.solc_path`, pragmas are checked against version of that
+/// binary instead of the registry.
+pub fn plan(
+    snapper: &SnapperFile,
+    sources: &Sources,
+    versions: &CompilerVersions,
+) -> Result<Vec<Job>> {
+    let solidity = &snapper.solidity;
+
+    let local = match &solidity.solc_path {
+        Some(p) => {
+            let version = utils::solc_version(Path::new(p))?;
+            Some((Version::parse(&version)?, p.clone()))
+        }
+        None => None,
+    };
+
+    let resolve = |spec: &str, sources: &Sources| -> Result<()> {
+        match &local {
+            Some((version, path)) => {
+                pragma::select_version(spec, sources, std::slice::from_ref(version), path)?;
+            }
+            None => {
+                pragma::resolve_build(spec, sources, versions)?;
+            }
+        }
+
+        Ok(())
+    };
+
+    let mut jobs: Vec<Job> = Vec::new();
+
+    let default = Compiler::default();
+    let compilers = std::iter::once(&default).chain(solidity.compilers.iter());
+
+    for (i, compiler) in compilers.enumerate() {
+        let name = if i == 0 {
+            "default".to_string()
+        } else {
+            format!("compiler-{i}")
+        };
+
+        jobs.push(Job {
+            name,
+            snapper: snapper.with_compiler(compiler),
+            roots: BTreeSet::new(),
+        });
+    }
+
+    let compilers_len = jobs.len();
+
+    for (i, (file, compiler)) in solidity.overrides.iter().enumerate() {
+        if !sources.is_root(file) {
+            bail!("`{file}` in `[solidity.overrides]` matches no source");
+        }
+
+        jobs.push(Job {
+            name: format!("override-{i}"),
+            snapper: snapper.with_compiler(compiler),
+            roots: BTreeSet::from([file.clone()]),
+        });
+    }
+
+    for name in sources.roots() {
+        if solidity.overrides.contains_key(name) {
+            continue;
+        }
+
+        let subset = sources.subset([name]);
+
+        let job = jobs[..compilers_len]
+            .iter_mut()
+            .find(|job| resolve(&job.snapper.solidity.version, &subset).is_ok());
+
+        match job {
+            Some(job) => {
+                job.roots.insert(name.clone());
+            }
+            None => {
+                // Report conflict with default compiler.
+                resolve(&solidity.version, &subset)?;
+            }
+        }
+    }
+
+    jobs.retain(|job| !job.roots.is_empty());
+
+    Ok(jobs)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, fs};
+
+    use snapper_solc::{utils, CompilerVersions, Sources};
+
+    use super::plan;
+
+    #[test]
+    fn test_overrides() {
+        let dir = std::env::temp_dir().join("snapper-jobs-overrides");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("A.sol"),
+            "pragma solidity ^0.8.0;\ncontract A {}\n",
+        )
+        .unwrap();
+
+        let mut sources = Sources::new(&dir);
+        sources.add_dir(&dir).unwrap();
+
+        let versions = CompilerVersions {
+            builds: HashMap::new(),
+        };
+
+        let snapper = utils::load_snapper_file(
+            r#"
+            [project]
+
+            [solidity]
+            version = "0.8.17"
+
+            [solidity.overrides."B.sol"]
+            version = "0.8.17"
+
+            [networks]
+            "#,
+        )
+        .unwrap();
+
+        let err = plan(&snapper, &sources, &versions).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "`B.sol` in `[solidity.overrides]` matches no source"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_solc_path() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join("snapper-jobs-solc-path");
+        let contracts = dir.join("contracts");
+        fs::create_dir_all(&contracts).unwrap();
+        fs::write(
+            contracts.join("A.sol"),
+            "pragma solidity ^0.8.26;\ncontract A {}\n",
+        )
+        .unwrap();
+
+        let solc = dir.join("solc");
+        fs::write(
+            &solc,
+            "#!/bin/sh\necho 'solc, the solidity compiler commandline interface'\n\
+             echo 'Version: 0.8.26+commit.8a97fa7a.Linux.g++'\n",
+        )
+        .unwrap();
+        fs::set_permissions(&solc, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut sources = Sources::new(&contracts);
+        sources.add_dir(&contracts).unwrap();
+
+        // Registry has no build of 0.8.26.
+        let versions = CompilerVersions {
+            builds: HashMap::new(),
+        };
+
+        let snapper = utils::load_snapper_file(&format!(
+            r#"
+            [project]
+
+            [solidity]
+            version = "auto"
+            offline = true
+            solc_path = "{}"
+
+            [networks]
+            "#,
+            solc.display()
+        ))
+        .unwrap();
+
+        let jobs = plan(&snapper, &sources, &versions).unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert!(jobs[0].roots.contains("A.sol"));
+
+        fs::write(
+            contracts.join("A.sol"),
+            "pragma solidity ^0.8.27;\ncontract A {}\n",
+        )
+        .unwrap();
+
+        let mut sources = Sources::new(&contracts);
+        sources.add_dir(&contracts).unwrap();
+
+        assert!(plan(&snapper, &sources, &versions).is_err());
+    }
+}
@@ -1,13 +1,27 @@
 use std::{
+    collections::BTreeMap,
     env, fs,
     path::{Path, PathBuf},
     str::FromStr,
 };
 
 use anyhow::{anyhow, Result};
-use ethers_contract_abigen::Abigen;
-use snapper_core::ProfileType;
-use snapper_solc::Solc;
+use snapper_core::{ProfileType, SnapperFile};
+use snapper_solc::{sources::artifact_dir, utils, CompilerVersions, Solc, Sources};
+
+mod jobs;
+
+mod generator;
+pub use generator::*;
+
+mod builtin;
+pub use builtin::*;
+
+mod constants;
+pub use constants::constants;
+
+mod modules;
+pub use modules::CONTRACTS_FILE;
 
 #[derive(Debug, Default)]
 pub struct Builder {
@@ -15,6 +29,7 @@ pub struct Builder {
     contract_path: Option<PathBuf>,
     bin_path: Option<PathBuf>,
     profile_type: Option<ProfileType>,
+    generator: Option<Box<dyn Generator>>,
 }
 
 impl Builder {
@@ -33,68 +48,90 @@ impl Builder {
         self
     }
 
-    fn walk_dir(&self, dir: &Path, solc: &Solc) -> Result<()> {
-        if dir.is_dir() {
-            let r = fs::read_dir(dir)?;
-
-            for dir in r {
-                let entry = dir?;
-                let path = entry.path();
-
-                if path.is_dir() {
-                    self.walk_dir(&path, solc)?;
-                } else {
-                    self.compile(&path, solc)?;
-                }
-            }
-        }
+    /// Profile in `[solidity.profiles]`, default is detected from cargo.
+    pub fn profile_type(&mut self, ty: ProfileType) -> &mut Self {
+        self.profile_type = Some(ty);
+        self
+    }
 
-        Ok(())
+    /// Binding generator, default is selected by `eth-*` features.
+    pub fn generator<G: Generator + 'static>(&mut self, generator: G) -> &mut Self {
+        self.generator = Some(Box::new(generator));
+        self
     }
 
-    fn compile(&self, file: &Path, solc: &Solc) -> Result<()> {
+    fn compile(&self, snapper: &SnapperFile, sources: &Sources) -> Result<()> {
         let profile_type = if let Some(p) = &self.profile_type {
             p.clone()
         } else {
-            let profile = env::var("PROFILE")?;
-            ProfileType::from_str(&profile).map_err(|e| anyhow!("{e}"))?
+            cargo_profile(snapper)?
         };
 
         let target_dir = temp_path();
 
-        let package_name = if let Some(name) = &solc.snapper.project.rename {
+        let package_name = if let Some(name) = &snapper.project.rename {
             name.to_string()
         } else {
             env::var("CARGO_PKG_NAME")?
         };
 
-        let filename = file.file_name().ok_or(anyhow!("Failed to get filename"))?;
-
         let out_dir = target_dir
             .join("snapper")
             .join("artifacts")
             .join(package_name);
 
-        let contracts = solc.compile(file, &profile_type, &out_dir)?;
+        let bin_path = if let Some(p) = &self.bin_path {
+            p.clone()
+        } else {
+            utils::default_solc_dir().unwrap_or_else(|_| target_dir.join("bin"))
+        };
+
+        let versions = CompilerVersions::load_for(snapper)?;
+
+        let mut contracts = BTreeMap::new();
+
+        for job in jobs::plan(snapper, sources, &versions)? {
+            let sources = sources.subset(&job.roots);
+
+            let mut solc = Solc::with_versions(&bin_path, &versions, job.snapper, &sources)?;
+            solc.name = job.name;
+
+            contracts.extend(solc.compile(&sources, &profile_type, &out_dir)?);
+        }
 
         // Abi generate.
-        for c in contracts {
-            let abi_path = out_dir.join(filename).join(format!("{c}.abi.json"));
+        let default_generator = generator::default_generator();
+        let generator = self.generator.as_ref().unwrap_or(&default_generator);
+
+        let binding_dir = PathBuf::from(env::var("OUT_DIR")?);
+        let mut bindings = Vec::new();
+
+        for (source, names) in contracts {
+            let target_dir = artifact_dir(&binding_dir, &source);
+            fs::create_dir_all(&target_dir)?;
 
-            let target_file = env::var("OUT_DIR")?;
-            let target_file = Path::new(&target_file).join(filename);
-            fs::create_dir_all(&target_file)?;
+            for name in names {
+                let target_file = target_dir.join(format!("{name}.rs"));
 
-            let target_file = target_file.join(format!("{c}.rs"));
+                if let Ok(p) = target_file.strip_prefix(&binding_dir) {
+                    bindings.push(p.to_string_lossy().to_string());
+                }
+
+                let contract = Contract {
+                    name,
+                    artifact_dir: artifact_dir(&out_dir, &source),
+                    source: source.clone(),
+                };
+
+                if !is_stale(&contract.inputs(), &target_file) {
+                    continue;
+                }
 
-            Abigen::new(c, abi_path.to_str().ok_or(anyhow!("Failed to get path"))?)
-                .map_err(|e| anyhow!("New Failed: {e}"))?
-                .generate()
-                .map_err(|e| anyhow!("Generate Failed: {e}"))?
-                .write_to_file(target_file)?;
+                generator.generate(&contract, &target_file)?;
+            }
         }
 
-        Ok(())
+        modules::write_contracts(&binding_dir, &bindings)
     }
 
     pub fn build(self) -> Result<()> {
@@ -106,28 +143,57 @@ impl Builder {
 
         println!("{:?}", snapper_path.canonicalize()?);
 
-        let snapper = fs::read_to_string(snapper_path)?;
+        let (snapper, vars) = utils::load_snapper_path_vars(&snapper_path)?;
 
-        let solc = {
-            let bin_path = if let Some(p) = &self.bin_path {
-                p.clone()
-            } else {
-                let target_dir = temp_path();
+        rerun_if_changed(&snapper_path);
 
-                target_dir.join("bin")
-            };
-            Solc::new(bin_path, None, &snapper)?
-        };
+        // Also when missing, so creating `.env` reruns. Cargo reruns every
+        // build until it exists.
+        rerun_if_changed(&snapper_path.with_file_name(".env"));
 
-        // Compile code
+        for var in ENV_VARS
+            .iter()
+            .copied()
+            .chain(vars.iter().map(String::as_str))
+        {
+            println!("cargo:rerun-if-env-changed={var}");
+        }
+
+        // Collect sources
         let contract_dir = if let Some(p) = &self.contract_path {
             p.clone()
         } else {
             PathBuf::from("contracts")
         };
 
-        self.walk_dir(&contract_dir, &solc)?;
-        Ok(())
+        let solidity = &snapper.solidity;
+
+        let base_path = if let Some(p) = &solidity.base_path {
+            PathBuf::from(p)
+        } else {
+            contract_dir.clone()
+        };
+
+        let mut sources = Sources::new(base_path);
+
+        for p in &solidity.include_paths {
+            sources.include_path(p);
+        }
+
+        for (prefix, target) in &solidity.remappings {
+            sources.remapping(prefix, target);
+        }
+
+        sources.add_dir(&contract_dir)?;
+
+        // Directory for added files, units for imports outside of it.
+        rerun_if_changed(&contract_dir);
+        for (_, unit) in sources.iter() {
+            rerun_if_changed(&unit.path);
+        }
+
+        // Compile code
+        self.compile(&snapper, &sources)
     }
 }
 
@@ -137,6 +203,68 @@ pub fn build() -> Result<()> {
     builder.build()
 }
 
+/// Environment variables read by `Builder::build`, besides variables
+/// interpolated in `Snapper.toml` and those set by cargo.
+const ENV_VARS: &[&str] = &[
+    "SNAPPER_PROFILE",
+    "SNAPPER_NETWORK",
+    "SNAPPER_OFFLINE",
+    "SNAPPER_HOME",
+    // Home directory of `~/.snapper` without `SNAPPER_HOME`.
+    "HOME",
+    "USERPROFILE",
+];
+
+fn rerun_if_changed(path: &Path) {
+    println!("cargo:rerun-if-changed={}", path.display());
+}
+
+/// Profile of cargo build, `SNAPPER_PROFILE` overrides it.
+///
+/// Custom cargo profiles, like `cargo build --profile ci`, use the same
+/// named profile of `Snapper.toml` if configured, otherwise `debug` or
+/// `release` by optimization of cargo profile.
+fn cargo_profile(snapper: &SnapperFile) -> Result<ProfileType> {
+    let parse = |name: &str| ProfileType::from_str(name).map_err(|e| anyhow!("{e}"));
+
+    if let Ok(name) = env::var("SNAPPER_PROFILE") {
+        return parse(&name);
+    }
+
+    // `OUT_DIR` is `<target>/<profile>/build/<package>/out`.
+    if let Ok(out_dir) = env::var("OUT_DIR") {
+        let name = Path::new(&out_dir)
+            .ancestors()
+            .find(|p| p.file_name().is_some_and(|n| n == "build"))
+            .and_then(|p| p.parent()?.file_name()?.to_str());
+
+        if let Some(name) = name {
+            if snapper.solidity.profiles.0.contains_key(name) {
+                return parse(name);
+            }
+        }
+    }
+
+    match env::var("PROFILE") {
+        Ok(profile) => parse(&profile),
+        Err(_) if env::var_os("CARGO_CFG_DEBUG_ASSERTIONS").is_some() => Ok(ProfileType::Debug),
+        Err(_) => Ok(ProfileType::Release),
+    }
+}
+
+/// Target is missing or older than any source.
+fn is_stale(sources: &[PathBuf], target: &Path) -> bool {
+    let modified = |p: &Path| fs::metadata(p).and_then(|m| m.modified()).ok();
+
+    let Some(target) = modified(target) else {
+        return true;
+    };
+
+    sources
+        .iter()
+        .any(|s| modified(s).map(|s| s > target).unwrap_or(true))
+}
+
 fn temp_path() -> PathBuf {
     Path::new(env!("OUT_DIR")).to_path_buf()
 }
@@ -0,0 +1,158 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Component, Path},
+};
+
+use anyhow::Result;
+
+use crate::builtin::ident;
+
+/// File including bindings of all contracts, under `OUT_DIR`.
+pub const CONTRACTS_FILE: &str = "snapper_contracts.rs";
+
+/// Module of directory or source file.
+#[derive(Debug, Default)]
+struct Module {
+    children: BTreeMap<String, Module>,
+    /// Binding files relative to `OUT_DIR`.
+    includes: Vec<String>,
+}
+
+/// Snake case of `name`, like `erc20_burnable` of `ERC20Burnable`.
+pub(crate) fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut res = String::new();
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                res.push('_');
+            }
+        }
+
+        if c.is_ascii_alphanumeric() {
+            res.push(c.to_ascii_lowercase());
+        } else {
+            res.push('_');
+        }
+    }
+
+    res
+}
+
+/// Module name of file or directory, like `erc20_burnable`.
+fn module_name(name: &str) -> String {
+    let mut res = snake_case(name);
+
+    if res.is_empty() || res == "_" || res.starts_with(|c: char| c.is_ascii_digit()) {
+        res.insert(0, '_');
+    }
+
+    ident(&res)
+}
+
+impl Module {
+    fn write(&self, res: &mut String, depth: usize) -> Result<()> {
+        let indent = "    ".repeat(depth);
+
+        for path in &self.includes {
+            writeln!(
+                res,
+                "{indent}include!(concat!(env!(\"OUT_DIR\"), {:?}));",
+                format!("/{path}")
+            )?;
+        }
+
+        for (name, module) in &self.children {
+            writeln!(res, "{indent}pub mod {name} {{")?;
+            module.write(res, depth + 1)?;
+            writeln!(res, "{indent}}}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Write `CONTRACTS_FILE` into `out_dir`, with a module for each directory
+/// and source file of `bindings`, which are paths of binding files relative
+/// to `out_dir`, like `token/ERC20.sol/ERC20.rs`.
+pub fn write_contracts(out_dir: &Path, bindings: &[String]) -> Result<()> {
+    let mut root = Module::default();
+
+    for binding in bindings {
+        let path = Path::new(binding);
+        let mut module = &mut root;
+
+        // Directories and source file, without binding file name.
+        if let Some(parent) = path.parent() {
+            for c in parent.components() {
+                if let Component::Normal(name) = c {
+                    let name = name.to_string_lossy();
+                    let name = name.strip_suffix(".sol").unwrap_or(&name);
+
+                    module = module.children.entry(module_name(name)).or_default();
+                }
+            }
+        }
+
+        module.includes.push(binding.replace('\\', "/"));
+    }
+
+    let mut code =
+        String::from("// Generated by snapper, include with `snapper::contracts!()`.\n\n");
+    root.write(&mut code, 0)?;
+
+    let path = out_dir.join(CONTRACTS_FILE);
+
+    if fs::read_to_string(&path).ok().as_deref() != Some(code.as_str()) {
+        fs::write(path, code)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{module_name, write_contracts, CONTRACTS_FILE};
+
+    #[test]
+    fn test_module_name() {
+        assert_eq!(module_name("Lock"), "lock");
+        assert_eq!(module_name("ERC20"), "erc20");
+        assert_eq!(module_name("ERC20Burnable"), "erc20_burnable");
+        assert_eq!(module_name("IUniswapV2Pair"), "i_uniswap_v2_pair");
+        assert_eq!(module_name("@openzeppelin"), "_openzeppelin");
+        assert_eq!(module_name("_"), "__");
+        assert_eq!(module_name("type"), "r#type");
+        assert_eq!(module_name("crate"), "crate_");
+        assert_eq!(module_name("Self"), "self_");
+    }
+
+    #[test]
+    fn test_write_contracts() {
+        let out_dir = std::env::temp_dir().join("snapper-contracts-test");
+        std::fs::create_dir_all(&out_dir).unwrap();
+
+        let bindings = [
+            "Lock.sol/Lock.rs".to_string(),
+            "token/ERC20.sol/ERC20.rs".to_string(),
+            "token/ERC20.sol/IERC20.rs".to_string(),
+        ];
+        write_contracts(&out_dir, &bindings).unwrap();
+
+        let code = std::fs::read_to_string(out_dir.join(CONTRACTS_FILE)).unwrap();
+        assert!(code.contains(
+            "pub mod lock {\n    include!(concat!(env!(\"OUT_DIR\"), \"/Lock.sol/Lock.rs\"));\n}"
+        ));
+        assert!(code.contains("pub mod token {\n    pub mod erc20 {\n"));
+        assert!(code.contains("\"/token/ERC20.sol/IERC20.rs\""));
+    }
+}
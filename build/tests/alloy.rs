@@ -0,0 +1,54 @@
+//! Bindings of `AlloyGenerator` compile. Fixture is regenerated by
+//! `SNAPPER_BLESS=1 cargo test -p snapper-build --features eth-alloy --test alloy`.
+
+use alloy::{primitives::U256, sol_types::SolCall};
+
+#[allow(dead_code)]
+mod lock {
+    include!("fixtures/Lock.alloy.rs");
+}
+
+#[cfg(feature = "eth-alloy")]
+#[test]
+fn test_fixture() {
+    use std::{env, fs};
+
+    use snapper_build::{AlloyGenerator, Contract};
+
+    // Relative to package, as `sol!` resolves the abi path.
+    let contract = Contract {
+        name: "Lock".to_string(),
+        source: "Lock.sol".to_string(),
+        artifact_dir: "tests/fixtures".into(),
+    };
+    let code = AlloyGenerator.code(&contract).unwrap();
+
+    let fixture = "tests/fixtures/Lock.alloy.rs";
+    if env::var_os("SNAPPER_BLESS").is_some() {
+        fs::write(fixture, &code).unwrap();
+    }
+    assert_eq!(
+        fs::read_to_string(fixture).unwrap(),
+        code,
+        "Lock.alloy.rs is outdated, rerun with SNAPPER_BLESS=1"
+    );
+}
+
+#[test]
+fn test_lock() {
+    use lock::Lock;
+
+    assert_eq!(Lock::BYTECODE[..], [0x60, 0x80]);
+    assert!(Lock::DEPLOYED_BYTECODE.is_empty());
+    assert_eq!(Lock::WITHDRAW_1_SELECTOR, Lock::withdraw_1Call::SELECTOR);
+    assert!(Lock::ABI_JSON.contains("\"Withdrawal\""));
+
+    let call = Lock::withdraw_1Call {
+        amount: U256::from(7),
+    };
+    let data = call.abi_encode();
+    assert_eq!(
+        Lock::withdraw_1Call::abi_decode(&data).unwrap().amount,
+        call.amount
+    );
+}
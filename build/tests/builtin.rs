@@ -0,0 +1,95 @@
+//! Bindings of `BuiltinGenerator` compile and round trip. Fixtures are
+//! regenerated by `SNAPPER_BLESS=1 cargo test -p snapper-build --test builtin`.
+
+extern crate snapper_core as snapper;
+
+use std::{env, fs, path::Path};
+
+use snapper::abi::{self, AbiType, Address, FixedBytes, U256};
+use snapper_build::BuiltinGenerator;
+use snapper_solc::output::ABIInfo;
+
+#[allow(dead_code)]
+mod lock {
+    include!("fixtures/Lock.rs");
+}
+
+#[allow(dead_code)]
+mod names {
+    include!("fixtures/Names.rs");
+}
+
+#[test]
+fn test_fixtures() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    for (name, abi) in [
+        ("Lock", dir.join("src/abi.json")),
+        ("Names", dir.join("tests/fixtures/Names.abi.json")),
+    ] {
+        let abi: Vec<ABIInfo> = serde_json::from_slice(&fs::read(abi).unwrap()).unwrap();
+        let code = BuiltinGenerator.code(name, &abi).unwrap();
+
+        let fixture = dir.join(format!("tests/fixtures/{name}.rs"));
+        if env::var_os("SNAPPER_BLESS").is_some() {
+            fs::write(&fixture, &code).unwrap();
+        }
+        assert_eq!(
+            fs::read_to_string(&fixture).unwrap(),
+            code,
+            "{name}.rs is outdated, rerun with SNAPPER_BLESS=1"
+        );
+    }
+}
+
+#[test]
+fn test_lock() {
+    use lock::Lock;
+
+    let call = Lock::withdraw_1Call {
+        amount: U256::from(7u128),
+    };
+    let data = call.encode();
+    assert_eq!(data[..4], Lock::withdraw_1Call::SELECTOR);
+    assert_eq!(Lock::withdraw_1Call::decode(&data).unwrap(), call);
+    assert!(Lock::withdraw_0Call::decode(&data).is_err());
+
+    let owner = Address([0x11; 20]);
+    let returned = abi::encode_params(&Lock::ownerReturn { _0: owner });
+    assert_eq!(
+        Lock::ownerCall::decode_returns(&returned).unwrap()._0,
+        owner
+    );
+
+    let tags = vec![FixedBytes([0x22; 32])];
+    let data = abi::encode(&[U256::from(1u128).to_token(), tags.to_token()]);
+    let topics = [Lock::Withdrawal::TOPIC, U256::from(2u128).0, [0x33; 32]];
+    let log = Lock::Withdrawal::decode_log(&topics, &data).unwrap();
+    assert_eq!(log.amount, U256::from(1u128));
+    assert_eq!(log.when, 2);
+    assert_eq!(log.memo, FixedBytes([0x33; 32]));
+    assert_eq!(log.tags, tags);
+}
+
+#[test]
+fn test_names() {
+    use names::Names;
+
+    let call = Names::moveCall {
+        self_: Address([0x44; 20]),
+        crate_: U256::from(5u128),
+        super_: FixedBytes([0x55; 32]),
+        Self_: true,
+        r#type: "type".to_string(),
+        a: Names::A_Info {
+            x: U256::from(6u128),
+        },
+        b: vec![Names::B_Info { y: true }, Names::B_Info { y: false }],
+    };
+    assert_eq!(Names::moveCall::decode(&call.encode()).unwrap(), call);
+
+    let error = Names::Info {
+        super_: "reason".to_string(),
+    };
+    assert_eq!(Names::Info::decode(&error.encode()).unwrap(), error);
+}
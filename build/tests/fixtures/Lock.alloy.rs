@@ -0,0 +1,33 @@
+/// Bindings of `Lock`.
+#[allow(non_snake_case)]
+pub mod Lock {
+    alloy::sol!(
+        #[allow(missing_docs, clippy::all)]
+        #[sol(rpc, bytecode = "6080")]
+        Lock,
+        "tests/fixtures/Lock.abi.json"
+    );
+
+    pub use self::Lock::*;
+
+    /// Deployed bytecode of `Lock`, empty if not selected or unlinked.
+    pub static DEPLOYED_BYTECODE: alloy::primitives::Bytes = alloy::primitives::Bytes::new();
+
+    /// Abi of `Lock` as json.
+    pub const ABI_JSON: &str = "[\n  {\"type\":\"constructor\",\"stateMutability\":\"payable\",\"inputs\":[{\"name\":\"_unlockTime\",\"type\":\"uint256\",\"internalType\":\"uint256\"}]},\n  {\"type\":\"error\",\"name\":\"Unauthorized\",\"inputs\":[{\"name\":\"caller\",\"type\":\"address\",\"internalType\":\"address\"},{\"name\":\"reason\",\"type\":\"string\",\"internalType\":\"string\"}]},\n  {\"type\":\"event\",\"name\":\"Withdrawal\",\"anonymous\":false,\"inputs\":[{\"name\":\"amount\",\"type\":\"uint256\",\"indexed\":false,\"internalType\":\"uint256\"},{\"name\":\"when\",\"type\":\"uint64\",\"indexed\":true,\"internalType\":\"uint64\"},{\"name\":\"memo\",\"type\":\"string\",\"indexed\":true,\"internalType\":\"string\"},{\"name\":\"tags\",\"type\":\"bytes32[]\",\"indexed\":false,\"internalType\":\"bytes32[]\"}]},\n  {\"type\":\"function\",\"name\":\"owner\",\"stateMutability\":\"view\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address payable\"}]},\n  {\"type\":\"function\",\"name\":\"withdraw\",\"stateMutability\":\"nonpayable\",\"inputs\":[],\"outputs\":[]},\n  {\"type\":\"function\",\"name\":\"withdraw\",\"stateMutability\":\"nonpayable\",\"inputs\":[{\"name\":\"amount\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[]},\n  {\"type\":\"function\",\"name\":\"info\",\"stateMutability\":\"view\",\"inputs\":[{\"name\":\"kind\",\"type\":\"int24\",\"internalType\":\"int24\"}],\"outputs\":[\n    {\"name\":\"\",\"type\":\"tuple[]\",\"internalType\":\"struct Lock.Info[]\",\"components\":[\n      {\"name\":\"owner\",\"type\":\"address\",\"internalType\":\"address\"},\n      {\"name\":\"names\",\"type\":\"string[2]\",\"internalType\":\"string[2]\"},\n      {\"name\":\"inner\",\"type\":\"tuple\",\"internalType\":\"struct Lock.Inner\",\"components\":[\n        {\"name\":\"data\",\"type\":\"bytes\",\"internalType\":\"bytes\"},\n        {\"name\":\"flag\",\"type\":\"bool\",\"internalType\":\"bool\"}\n      ]}\n    ]}\n  ]}\n]\n";
+
+    /// Metadata of `Lock` as json, empty if not selected.
+    pub const METADATA: &str = "";
+
+    /// Selector of `owner()`.
+    pub const OWNER_SELECTOR: [u8; 4] = [0x8d, 0xa5, 0xcb, 0x5b];
+
+    /// Selector of `withdraw()`.
+    pub const WITHDRAW_0_SELECTOR: [u8; 4] = [0x3c, 0xcf, 0xd6, 0x0b];
+
+    /// Selector of `withdraw(uint256)`.
+    pub const WITHDRAW_1_SELECTOR: [u8; 4] = [0x2e, 0x1a, 0x7d, 0x4d];
+
+    /// Selector of `info(int24)`.
+    pub const INFO_SELECTOR: [u8; 4] = [0x38, 0x33, 0x33, 0xd0];
+}
@@ -0,0 +1,433 @@
+/// Bindings of `Lock`.
+#[allow(non_camel_case_types, non_snake_case, unused_mut, unused_variables, clippy::all)]
+pub mod Lock {
+    use ::snapper::abi::{self, AbiType};
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Info {
+        pub owner: abi::Address,
+        pub names: [String; 2],
+        pub inner: Inner,
+    }
+
+    impl AbiType for Info {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<abi::Address as AbiType>::param_type(), <[String; 2] as AbiType>::param_type(), <Inner as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self.owner.to_token(), self.names.to_token(), self.inner.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { owner: abi::next(&mut tokens)?, names: abi::next(&mut tokens)?, inner: abi::next(&mut tokens)? })
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Inner {
+        pub data: abi::Bytes,
+        pub flag: bool,
+    }
+
+    impl AbiType for Inner {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<abi::Bytes as AbiType>::param_type(), <bool as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self.data.to_token(), self.flag.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { data: abi::next(&mut tokens)?, flag: abi::next(&mut tokens)? })
+        }
+    }
+
+    /// Constructor arguments.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct constructorCall {
+        pub _unlockTime: abi::U256,
+    }
+
+    impl AbiType for constructorCall {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<abi::U256 as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self._unlockTime.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { _unlockTime: abi::next(&mut tokens)? })
+        }
+    }
+
+    impl constructorCall {
+        /// Deploy code, `bytecode` followed by arguments.
+        pub fn deploy_code(&self, bytecode: &[u8]) -> Vec<u8> {
+            let mut res = bytecode.to_vec();
+            res.extend_from_slice(&abi::encode_params(self));
+            res
+        }
+    }
+
+    /// Error `Unauthorized(address,string)`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Unauthorized {
+        pub caller: abi::Address,
+        pub reason: String,
+    }
+
+    impl AbiType for Unauthorized {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<abi::Address as AbiType>::param_type(), <String as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self.caller.to_token(), self.reason.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { caller: abi::next(&mut tokens)?, reason: abi::next(&mut tokens)? })
+        }
+    }
+
+    impl Unauthorized {
+        pub const SIGNATURE: &'static str = "Unauthorized(address,string)";
+        pub const SELECTOR: [u8; 4] = [0xa3, 0x5b, 0x15, 0x0b];
+
+        /// Revert data with selector.
+        pub fn encode(&self) -> Vec<u8> {
+            abi::encode_call(&Self::SELECTOR, self)
+        }
+
+        pub fn decode(data: &[u8]) -> abi::Result<Self> {
+            abi::decode_call(&Self::SELECTOR, data)
+        }
+    }
+
+    /// Event `Withdrawal(uint256,uint64,string,bytes32[])`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Withdrawal {
+        pub amount: abi::U256,
+        pub when: u64,
+        pub memo: abi::FixedBytes<32>,
+        pub tags: Vec<abi::FixedBytes<32>>,
+    }
+
+    impl Withdrawal {
+        pub const SIGNATURE: &'static str = "Withdrawal(uint256,uint64,string,bytes32[])";
+        /// First topic of log, unless event is anonymous.
+        pub const TOPIC: [u8; 32] = [0x62, 0xdf, 0xd3, 0x75, 0x23, 0x40, 0x4d, 0x9f, 0x26, 0x2c, 0xd5, 0x4b, 0x0a, 0x78, 0xa9, 0xb3, 0xb5, 0x8a, 0xf4, 0x1d, 0x07, 0xce, 0xe1, 0xe1, 0xfa, 0x19, 0x7f, 0x1d, 0x6a, 0x01, 0xd5, 0x3f];
+
+        pub fn decode_log(topics: &[[u8; 32]], data: &[u8]) -> abi::Result<Self> {
+            let mut topics = topics.iter();
+
+            if topics.next() != Some(&Self::TOPIC) {
+                return Err(abi::Error::InvalidAbiData);
+            }
+
+            let mut data = abi::decode(&[<abi::U256 as AbiType>::param_type(), <Vec<abi::FixedBytes<32>> as AbiType>::param_type()], data)?.into_iter();
+
+            Ok(Self { amount: abi::next(&mut data)?, when: abi::topic(topics.next())?, memo: abi::topic(topics.next())?, tags: abi::next(&mut data)? })
+        }
+    }
+
+    /// Call `owner()`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ownerCall {}
+
+    impl AbiType for ownerCall {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self {  })
+        }
+    }
+
+    impl ownerCall {
+        pub const SIGNATURE: &'static str = "owner()";
+        pub const SELECTOR: [u8; 4] = [0x8d, 0xa5, 0xcb, 0x5b];
+
+        /// Calldata with selector.
+        pub fn encode(&self) -> Vec<u8> {
+            abi::encode_call(&Self::SELECTOR, self)
+        }
+
+        pub fn decode(data: &[u8]) -> abi::Result<Self> {
+            abi::decode_call(&Self::SELECTOR, data)
+        }
+
+        pub fn decode_returns(data: &[u8]) -> abi::Result<ownerReturn> {
+            abi::decode_params(data)
+        }
+    }
+
+    /// Return of `owner()`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ownerReturn {
+        pub _0: abi::Address,
+    }
+
+    impl AbiType for ownerReturn {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<abi::Address as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self._0.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { _0: abi::next(&mut tokens)? })
+        }
+    }
+
+    /// Call `withdraw()`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct withdraw_0Call {}
+
+    impl AbiType for withdraw_0Call {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self {  })
+        }
+    }
+
+    impl withdraw_0Call {
+        pub const SIGNATURE: &'static str = "withdraw()";
+        pub const SELECTOR: [u8; 4] = [0x3c, 0xcf, 0xd6, 0x0b];
+
+        /// Calldata with selector.
+        pub fn encode(&self) -> Vec<u8> {
+            abi::encode_call(&Self::SELECTOR, self)
+        }
+
+        pub fn decode(data: &[u8]) -> abi::Result<Self> {
+            abi::decode_call(&Self::SELECTOR, data)
+        }
+
+        pub fn decode_returns(data: &[u8]) -> abi::Result<withdraw_0Return> {
+            abi::decode_params(data)
+        }
+    }
+
+    /// Return of `withdraw()`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct withdraw_0Return {}
+
+    impl AbiType for withdraw_0Return {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self {  })
+        }
+    }
+
+    /// Call `withdraw(uint256)`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct withdraw_1Call {
+        pub amount: abi::U256,
+    }
+
+    impl AbiType for withdraw_1Call {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<abi::U256 as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self.amount.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { amount: abi::next(&mut tokens)? })
+        }
+    }
+
+    impl withdraw_1Call {
+        pub const SIGNATURE: &'static str = "withdraw(uint256)";
+        pub const SELECTOR: [u8; 4] = [0x2e, 0x1a, 0x7d, 0x4d];
+
+        /// Calldata with selector.
+        pub fn encode(&self) -> Vec<u8> {
+            abi::encode_call(&Self::SELECTOR, self)
+        }
+
+        pub fn decode(data: &[u8]) -> abi::Result<Self> {
+            abi::decode_call(&Self::SELECTOR, data)
+        }
+
+        pub fn decode_returns(data: &[u8]) -> abi::Result<withdraw_1Return> {
+            abi::decode_params(data)
+        }
+    }
+
+    /// Return of `withdraw(uint256)`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct withdraw_1Return {}
+
+    impl AbiType for withdraw_1Return {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self {  })
+        }
+    }
+
+    /// Call `info(int24)`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct infoCall {
+        pub r#type: i32,
+    }
+
+    impl AbiType for infoCall {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<i32 as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self.r#type.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { r#type: abi::next(&mut tokens)? })
+        }
+    }
+
+    impl infoCall {
+        pub const SIGNATURE: &'static str = "info(int24)";
+        pub const SELECTOR: [u8; 4] = [0x38, 0x33, 0x33, 0xd0];
+
+        /// Calldata with selector.
+        pub fn encode(&self) -> Vec<u8> {
+            abi::encode_call(&Self::SELECTOR, self)
+        }
+
+        pub fn decode(data: &[u8]) -> abi::Result<Self> {
+            abi::decode_call(&Self::SELECTOR, data)
+        }
+
+        pub fn decode_returns(data: &[u8]) -> abi::Result<infoReturn> {
+            abi::decode_params(data)
+        }
+    }
+
+    /// Return of `info(int24)`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct infoReturn {
+        pub _0: Vec<Info>,
+    }
+
+    impl AbiType for infoReturn {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<Vec<Info> as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self._0.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { _0: abi::next(&mut tokens)? })
+        }
+    }
+}
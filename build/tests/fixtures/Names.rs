@@ -0,0 +1,221 @@
+/// Bindings of `Names`.
+#[allow(non_camel_case_types, non_snake_case, unused_mut, unused_variables, clippy::all)]
+pub mod Names {
+    use ::snapper::abi::{self, AbiType};
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct A_Info {
+        pub x: abi::U256,
+    }
+
+    impl AbiType for A_Info {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<abi::U256 as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self.x.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { x: abi::next(&mut tokens)? })
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct A_Moved {
+        pub self_: abi::Address,
+    }
+
+    impl AbiType for A_Moved {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<abi::Address as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self.self_.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { self_: abi::next(&mut tokens)? })
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct B_Info {
+        pub y: bool,
+    }
+
+    impl AbiType for B_Info {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<bool as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self.y.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { y: abi::next(&mut tokens)? })
+        }
+    }
+
+    /// Call `move(address,uint256,bytes32,bool,string,(uint256),(bool)[])`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct moveCall {
+        pub self_: abi::Address,
+        pub crate_: abi::U256,
+        pub super_: abi::FixedBytes<32>,
+        pub Self_: bool,
+        pub r#type: String,
+        pub a: A_Info,
+        pub b: Vec<B_Info>,
+    }
+
+    impl AbiType for moveCall {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<abi::Address as AbiType>::param_type(), <abi::U256 as AbiType>::param_type(), <abi::FixedBytes<32> as AbiType>::param_type(), <bool as AbiType>::param_type(), <String as AbiType>::param_type(), <A_Info as AbiType>::param_type(), <Vec<B_Info> as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self.self_.to_token(), self.crate_.to_token(), self.super_.to_token(), self.Self_.to_token(), self.r#type.to_token(), self.a.to_token(), self.b.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { self_: abi::next(&mut tokens)?, crate_: abi::next(&mut tokens)?, super_: abi::next(&mut tokens)?, Self_: abi::next(&mut tokens)?, r#type: abi::next(&mut tokens)?, a: abi::next(&mut tokens)?, b: abi::next(&mut tokens)? })
+        }
+    }
+
+    impl moveCall {
+        pub const SIGNATURE: &'static str = "move(address,uint256,bytes32,bool,string,(uint256),(bool)[])";
+        pub const SELECTOR: [u8; 4] = [0x65, 0x66, 0xeb, 0xf9];
+
+        /// Calldata with selector.
+        pub fn encode(&self) -> Vec<u8> {
+            abi::encode_call(&Self::SELECTOR, self)
+        }
+
+        pub fn decode(data: &[u8]) -> abi::Result<Self> {
+            abi::decode_call(&Self::SELECTOR, data)
+        }
+
+        pub fn decode_returns(data: &[u8]) -> abi::Result<moveReturn> {
+            abi::decode_params(data)
+        }
+    }
+
+    /// Return of `move(address,uint256,bytes32,bool,string,(uint256),(bool)[])`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct moveReturn {
+        pub _0: A_Moved,
+    }
+
+    impl AbiType for moveReturn {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<A_Moved as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self._0.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { _0: abi::next(&mut tokens)? })
+        }
+    }
+
+    /// Event `Moved(address,uint256)`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Moved {
+        pub self_: abi::Address,
+        pub crate_: abi::U256,
+    }
+
+    impl Moved {
+        pub const SIGNATURE: &'static str = "Moved(address,uint256)";
+        /// First topic of log, unless event is anonymous.
+        pub const TOPIC: [u8; 32] = [0x5f, 0x8c, 0x32, 0x6f, 0x85, 0x5a, 0x74, 0xf7, 0xf4, 0x75, 0x86, 0xfc, 0x81, 0x87, 0x06, 0x63, 0x69, 0x22, 0x68, 0xd1, 0x5d, 0x98, 0xe8, 0xf2, 0x0c, 0x0a, 0xb3, 0x84, 0x76, 0x41, 0xa6, 0xe7];
+
+        pub fn decode_log(topics: &[[u8; 32]], data: &[u8]) -> abi::Result<Self> {
+            let mut topics = topics.iter();
+
+            if topics.next() != Some(&Self::TOPIC) {
+                return Err(abi::Error::InvalidAbiData);
+            }
+
+            let mut data = abi::decode(&[<abi::U256 as AbiType>::param_type()], data)?.into_iter();
+
+            Ok(Self { self_: abi::topic(topics.next())?, crate_: abi::next(&mut data)? })
+        }
+    }
+
+    /// Error `Info(string)`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Info {
+        pub super_: String,
+    }
+
+    impl AbiType for Info {
+        fn param_type() -> abi::ParamType {
+            abi::ParamType::Tuple(vec![<String as AbiType>::param_type()])
+        }
+
+        fn to_token(&self) -> abi::Token {
+            abi::Token::Tuple(vec![self.super_.to_token()])
+        }
+
+        fn from_token(token: abi::Token) -> abi::Result<Self> {
+            let abi::Token::Tuple(tokens) = token else {
+                return Err(abi::Error::InvalidAbiData);
+            };
+
+            let mut tokens = tokens.into_iter();
+
+            Ok(Self { super_: abi::next(&mut tokens)? })
+        }
+    }
+
+    impl Info {
+        pub const SIGNATURE: &'static str = "Info(string)";
+        pub const SELECTOR: [u8; 4] = [0x6d, 0x12, 0x8f, 0x20];
+
+        /// Revert data with selector.
+        pub fn encode(&self) -> Vec<u8> {
+            abi::encode_call(&Self::SELECTOR, self)
+        }
+
+        pub fn decode(data: &[u8]) -> abi::Result<Self> {
+            abi::decode_call(&Self::SELECTOR, data)
+        }
+    }
+}
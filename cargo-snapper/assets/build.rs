@@ -1,8 +1,3 @@
 fn main() {
     snapper::build().expect("Build snapper project failed");
-
-    println!("cargo:rerun-if-changed=contracts/");
-    println!("cargo:rerun-if-changed=scripts/");
-    println!("cargo:rerun-if-changed=build.rs");
-    println!("cargo:rerun-if-changed=Snapper.toml");
 }
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use clap::{Args, Parser, Subcommand};
 
-use super::{Init, New, Script};
+use super::{Build, Init, New, Script};
 
 #[derive(Debug, Parser)]
 #[command(author, version, about)]
@@ -50,6 +50,8 @@ pub enum SubCmd {
     New(New),
     /// Manage scripts.
     Script(Script),
+    /// Build contracts and package.
+    Build(Build),
     /// Manage network.
     Network,
     /// Manage library.
@@ -72,6 +74,7 @@ impl SubCmd {
             Self::Init(v) => v.execute(),
             Self::New(v) => v.execute(),
             Self::Script(v) => v.execute(),
+            Self::Build(v) => v.execute(),
             _ => Ok(()),
         }
     }
@@ -0,0 +1,36 @@
+use std::{env, process::Command};
+
+use anyhow::{anyhow, Result};
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub struct Build {
+    #[arg(short, long)]
+    /// Link libraries for this network, same as `SNAPPER_NETWORK`
+    network: Option<String>,
+
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    /// Arguments passed to `cargo build`
+    args: Vec<String>,
+}
+
+impl Build {
+    pub fn execute(self) -> Result<()> {
+        let cargo = env::var("CARGO")?;
+
+        let mut cmd = Command::new(cargo);
+
+        cmd.arg("build").args(&self.args);
+
+        if let Some(network) = self.network {
+            cmd.env("SNAPPER_NETWORK", network);
+        }
+
+        let result = cmd.status()?;
+        if !result.success() {
+            return Err(anyhow!("Failed to execute cargo"));
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 mod args;
 pub use args::*;
 
+mod build;
+pub use build::*;
+
 mod init;
 pub use init::*;
 
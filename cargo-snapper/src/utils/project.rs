@@ -36,6 +36,17 @@ pub fn create(root: &Path) -> Result<()> {
         include_str!("../../assets/Snapper.toml"),
     )?;
 
+    // Secrets referenced by `Snapper.toml` live in `.env`.
+    let gitignore_path = root.join(".gitignore");
+    let mut gitignore = fs::read_to_string(&gitignore_path).unwrap_or_default();
+    if !gitignore.lines().any(|l| l.trim() == ".env") {
+        if !gitignore.is_empty() && !gitignore.ends_with('\n') {
+            gitignore.push('\n');
+        }
+        gitignore.push_str(".env\n");
+        fs::write(&gitignore_path, gitignore)?;
+    }
+
     let cts_path = root.join("Cargo.toml");
 
     let cts = fs::read_to_string(&cts_path)?;
@@ -51,6 +62,11 @@ pub fn create(root: &Path) -> Result<()> {
     features.push("build");
     snapper["features"] = value(features);
 
+    // Bindings included by `snapper::contracts!()` refer to `::snapper`.
+    let snapper = &mut cargo_toml["dependencies"]["snapper"];
+    snapper["version"] = value("0.1");
+    snapper["git"] = value("https://github.com/tiannian/snapper");
+
     fs::write(&cts_path, cargo_toml.to_string())?;
 
     Ok(())
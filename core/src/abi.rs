@@ -0,0 +1,555 @@
+//! Solidity ABI encoding, used by bindings of built-in generator.
+
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+use crate::keccak256;
+
+pub use crate::{Address, Bytes, Error, Result};
+
+/// Type of ABI value, used to decode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamType {
+    Address,
+    Uint(usize),
+    Int(usize),
+    Bool,
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<ParamType>),
+    FixedArray(Box<ParamType>, usize),
+    Tuple(Vec<ParamType>),
+}
+
+impl ParamType {
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            Self::Bytes | Self::String | Self::Array(_) => true,
+            Self::FixedArray(ty, len) => *len > 0 && ty.is_dynamic(),
+            Self::Tuple(tys) => tys.iter().any(|t| t.is_dynamic()),
+            _ => false,
+        }
+    }
+
+    /// Size of head, all dynamic types are referenced by offset.
+    fn head_size(&self) -> usize {
+        match self {
+            _ if self.is_dynamic() => 32,
+            Self::FixedArray(ty, len) => ty.head_size() * len,
+            Self::Tuple(tys) => tys.iter().map(|t| t.head_size()).sum(),
+            _ => 32,
+        }
+    }
+}
+
+/// ABI value, integers are 32 bytes big endian.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Address(Address),
+    Uint([u8; 32]),
+    Int([u8; 32]),
+    Bool(bool),
+    FixedBytes(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+    Array(Vec<Token>),
+    FixedArray(Vec<Token>),
+    Tuple(Vec<Token>),
+}
+
+impl Token {
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            Self::Bytes(_) | Self::String(_) | Self::Array(_) => true,
+            Self::FixedArray(tokens) | Self::Tuple(tokens) => tokens.iter().any(|t| t.is_dynamic()),
+            _ => false,
+        }
+    }
+
+    fn head_size(&self) -> usize {
+        match self {
+            _ if self.is_dynamic() => 32,
+            Self::FixedArray(tokens) | Self::Tuple(tokens) => {
+                tokens.iter().map(|t| t.head_size()).sum()
+            }
+            _ => 32,
+        }
+    }
+}
+
+/// Rust type with ABI encoding.
+pub trait AbiType: Sized {
+    fn param_type() -> ParamType;
+
+    fn to_token(&self) -> Token;
+
+    fn from_token(token: Token) -> Result<Self>;
+}
+
+/// First 4 bytes of keccak-256 of `signature`, like `transfer(address,uint256)`.
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn word(n: usize) -> [u8; 32] {
+    let mut res = [0u8; 32];
+    res[24..].copy_from_slice(&(n as u64).to_be_bytes());
+    res
+}
+
+fn pad_right(out: &mut Vec<u8>, data: &[u8]) {
+    out.extend_from_slice(data);
+    out.resize(out.len() + (32 - data.len() % 32) % 32, 0);
+}
+
+/// Encode `tokens` as tuple, like function arguments.
+pub fn encode(tokens: &[Token]) -> Vec<u8> {
+    let head_size: usize = tokens.iter().map(|t| t.head_size()).sum();
+
+    let mut head = Vec::with_capacity(head_size);
+    let mut tail = Vec::new();
+
+    for token in tokens {
+        if token.is_dynamic() {
+            head.extend_from_slice(&word(head_size + tail.len()));
+            encode_token(&mut tail, token);
+        } else {
+            encode_token(&mut head, token);
+        }
+    }
+
+    head.extend_from_slice(&tail);
+    head
+}
+
+fn encode_token(out: &mut Vec<u8>, token: &Token) {
+    match token {
+        Token::Address(address) => {
+            out.extend_from_slice(&[0u8; 12]);
+            out.extend_from_slice(&address.0);
+        }
+        Token::Uint(v) | Token::Int(v) => out.extend_from_slice(v),
+        Token::Bool(v) => out.extend_from_slice(&word(*v as usize)),
+        Token::FixedBytes(v) => pad_right(out, v),
+        Token::Bytes(v) => {
+            out.extend_from_slice(&word(v.len()));
+            pad_right(out, v);
+        }
+        Token::String(v) => {
+            out.extend_from_slice(&word(v.len()));
+            pad_right(out, v.as_bytes());
+        }
+        Token::Array(tokens) => {
+            out.extend_from_slice(&word(tokens.len()));
+            out.extend_from_slice(&encode(tokens));
+        }
+        Token::FixedArray(tokens) | Token::Tuple(tokens) => out.extend_from_slice(&encode(tokens)),
+    }
+}
+
+/// Decode tuple of `types` from `data`, like function return values.
+pub fn decode(types: &[ParamType], data: &[u8]) -> Result<Vec<Token>> {
+    decode_tuple(types, data, 0)
+}
+
+fn read(data: &[u8], at: usize, len: usize) -> Result<&[u8]> {
+    let end = at.checked_add(len).ok_or(Error::InvalidAbiData)?;
+    data.get(at..end).ok_or(Error::InvalidAbiData)
+}
+
+fn read_usize(data: &[u8], at: usize) -> Result<usize> {
+    let word = read(data, at, 32)?;
+
+    if word[..24].iter().any(|b| *b != 0) {
+        return Err(Error::InvalidAbiData);
+    }
+
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&word[24..]);
+
+    usize::try_from(u64::from_be_bytes(bytes)).map_err(|_| Error::InvalidAbiData)
+}
+
+fn decode_tuple(types: &[ParamType], data: &[u8], base: usize) -> Result<Vec<Token>> {
+    let mut res = Vec::with_capacity(types.len());
+    let mut offset = base;
+
+    for ty in types {
+        if ty.is_dynamic() {
+            let at = base
+                .checked_add(read_usize(data, offset)?)
+                .ok_or(Error::InvalidAbiData)?;
+            res.push(decode_token(ty, data, at)?);
+        } else {
+            res.push(decode_token(ty, data, offset)?);
+        }
+
+        offset += ty.head_size();
+    }
+
+    Ok(res)
+}
+
+fn decode_token(ty: &ParamType, data: &[u8], at: usize) -> Result<Token> {
+    let word = || -> Result<[u8; 32]> {
+        let mut res = [0u8; 32];
+        res.copy_from_slice(read(data, at, 32)?);
+        Ok(res)
+    };
+
+    let res = match ty {
+        ParamType::Address => {
+            let mut address = [0u8; 20];
+            address.copy_from_slice(&word()?[12..]);
+            Token::Address(Address(address))
+        }
+        ParamType::Uint(_) => Token::Uint(word()?),
+        ParamType::Int(_) => Token::Int(word()?),
+        ParamType::Bool => match read_usize(data, at)? {
+            0 => Token::Bool(false),
+            1 => Token::Bool(true),
+            _ => return Err(Error::InvalidAbiData),
+        },
+        ParamType::FixedBytes(len) => Token::FixedBytes(word()?[..*len].to_vec()),
+        ParamType::Bytes => {
+            let len = read_usize(data, at)?;
+            Token::Bytes(read(data, at + 32, len)?.to_vec())
+        }
+        ParamType::String => {
+            let len = read_usize(data, at)?;
+            let bytes = read(data, at + 32, len)?.to_vec();
+            Token::String(String::from_utf8(bytes).map_err(|_| Error::InvalidAbiData)?)
+        }
+        ParamType::Array(ty) => {
+            let len = read_usize(data, at)?;
+
+            // Each element takes at least one word.
+            if len > data.len() / 32 {
+                return Err(Error::InvalidAbiData);
+            }
+
+            let types = vec![(**ty).clone(); len];
+            Token::Array(decode_tuple(&types, data, at + 32)?)
+        }
+        ParamType::FixedArray(ty, len) => {
+            let types = vec![(**ty).clone(); *len];
+            Token::FixedArray(decode_tuple(&types, data, at)?)
+        }
+        ParamType::Tuple(types) => Token::Tuple(decode_tuple(types, data, at)?),
+    };
+
+    Ok(res)
+}
+
+/// Unsigned 256 bits integer, big endian.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U256(pub [u8; 32]);
+
+/// Signed 256 bits integer, big endian two's complement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct I256(pub [u8; 32]);
+
+/// `bytesN`, `N` is 1 to 32.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedBytes<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> Default for FixedBytes<N> {
+    fn default() -> Self {
+        Self([0u8; N])
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(v: u128) -> Self {
+        let mut res = [0u8; 32];
+        res[16..].copy_from_slice(&v.to_be_bytes());
+        Self(res)
+    }
+}
+
+impl TryFrom<U256> for u128 {
+    type Error = Error;
+
+    fn try_from(v: U256) -> Result<Self> {
+        if v.0[..16].iter().any(|b| *b != 0) {
+            return Err(Error::InvalidAbiData);
+        }
+
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&v.0[16..]);
+        Ok(u128::from_be_bytes(bytes))
+    }
+}
+
+impl From<i128> for I256 {
+    fn from(v: i128) -> Self {
+        let mut res = if v < 0 { [0xffu8; 32] } else { [0u8; 32] };
+        res[16..].copy_from_slice(&v.to_be_bytes());
+        Self(res)
+    }
+}
+
+fn unexpected<T>() -> Result<T> {
+    Err(Error::InvalidAbiData)
+}
+
+macro_rules! impl_uint {
+    ($($ty:ty => $bits:expr),*) => {
+        $(impl AbiType for $ty {
+            fn param_type() -> ParamType {
+                ParamType::Uint($bits)
+            }
+
+            fn to_token(&self) -> Token {
+                Token::Uint(U256::from(*self as u128).0)
+            }
+
+            fn from_token(token: Token) -> Result<Self> {
+                match token {
+                    Token::Uint(v) => u128::try_from(U256(v))?
+                        .try_into()
+                        .map_err(|_| Error::InvalidAbiData),
+                    _ => unexpected(),
+                }
+            }
+        })*
+    };
+}
+
+impl_uint!(u8 => 8, u16 => 16, u32 => 32, u64 => 64, u128 => 128);
+
+macro_rules! impl_int {
+    ($($ty:ty => $bits:expr),*) => {
+        $(impl AbiType for $ty {
+            fn param_type() -> ParamType {
+                ParamType::Int($bits)
+            }
+
+            fn to_token(&self) -> Token {
+                Token::Int(I256::from(*self as i128).0)
+            }
+
+            fn from_token(token: Token) -> Result<Self> {
+                let Token::Int(v) = token else {
+                    return unexpected();
+                };
+
+                let sign = if v[16] & 0x80 == 0 { 0 } else { 0xff };
+                if v[..16].iter().any(|b| *b != sign) {
+                    return unexpected();
+                }
+
+                let mut bytes = [0u8; 16];
+                bytes.copy_from_slice(&v[16..]);
+
+                i128::from_be_bytes(bytes)
+                    .try_into()
+                    .map_err(|_| Error::InvalidAbiData)
+            }
+        })*
+    };
+}
+
+impl_int!(i8 => 8, i16 => 16, i32 => 32, i64 => 64, i128 => 128);
+
+impl AbiType for U256 {
+    fn param_type() -> ParamType {
+        ParamType::Uint(256)
+    }
+
+    fn to_token(&self) -> Token {
+        Token::Uint(self.0)
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::Uint(v) => Ok(Self(v)),
+            _ => unexpected(),
+        }
+    }
+}
+
+impl AbiType for I256 {
+    fn param_type() -> ParamType {
+        ParamType::Int(256)
+    }
+
+    fn to_token(&self) -> Token {
+        Token::Int(self.0)
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::Int(v) => Ok(Self(v)),
+            _ => unexpected(),
+        }
+    }
+}
+
+impl AbiType for Address {
+    fn param_type() -> ParamType {
+        ParamType::Address
+    }
+
+    fn to_token(&self) -> Token {
+        Token::Address(*self)
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::Address(v) => Ok(v),
+            _ => unexpected(),
+        }
+    }
+}
+
+impl AbiType for bool {
+    fn param_type() -> ParamType {
+        ParamType::Bool
+    }
+
+    fn to_token(&self) -> Token {
+        Token::Bool(*self)
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::Bool(v) => Ok(v),
+            _ => unexpected(),
+        }
+    }
+}
+
+impl<const N: usize> AbiType for FixedBytes<N> {
+    fn param_type() -> ParamType {
+        ParamType::FixedBytes(N)
+    }
+
+    fn to_token(&self) -> Token {
+        Token::FixedBytes(self.0.to_vec())
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::FixedBytes(v) => v.try_into().map(Self).map_err(|_| Error::InvalidAbiData),
+            _ => unexpected(),
+        }
+    }
+}
+
+impl AbiType for Bytes {
+    fn param_type() -> ParamType {
+        ParamType::Bytes
+    }
+
+    fn to_token(&self) -> Token {
+        Token::Bytes(self.0.clone())
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::Bytes(v) => Ok(Self(v)),
+            _ => unexpected(),
+        }
+    }
+}
+
+impl AbiType for String {
+    fn param_type() -> ParamType {
+        ParamType::String
+    }
+
+    fn to_token(&self) -> Token {
+        Token::String(self.clone())
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::String(v) => Ok(v),
+            _ => unexpected(),
+        }
+    }
+}
+
+impl<T: AbiType> AbiType for Vec<T> {
+    fn param_type() -> ParamType {
+        ParamType::Array(Box::new(T::param_type()))
+    }
+
+    fn to_token(&self) -> Token {
+        Token::Array(self.iter().map(|v| v.to_token()).collect())
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::Array(tokens) => tokens.into_iter().map(T::from_token).collect(),
+            _ => unexpected(),
+        }
+    }
+}
+
+impl<T: AbiType, const N: usize> AbiType for [T; N] {
+    fn param_type() -> ParamType {
+        ParamType::FixedArray(Box::new(T::param_type()), N)
+    }
+
+    fn to_token(&self) -> Token {
+        Token::FixedArray(self.iter().map(|v| v.to_token()).collect())
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        let Token::FixedArray(tokens) = token else {
+            return unexpected();
+        };
+
+        let values = tokens
+            .into_iter()
+            .map(T::from_token)
+            .collect::<Result<Vec<_>>>()?;
+
+        values.try_into().map_err(|_| Error::InvalidAbiData)
+    }
+}
+
+/// Take next token of tuple, used by generated bindings.
+pub fn next<T: AbiType>(tokens: &mut impl Iterator<Item = Token>) -> Result<T> {
+    T::from_token(tokens.next().ok_or(Error::InvalidAbiData)?)
+}
+
+/// Encode fields of tuple type `value`, like function arguments.
+pub fn encode_params<T: AbiType>(value: &T) -> Vec<u8> {
+    match value.to_token() {
+        Token::Tuple(tokens) => encode(&tokens),
+        token => encode(&[token]),
+    }
+}
+
+/// Decode tuple type `T` from its fields, like function return values.
+pub fn decode_params<T: AbiType>(data: &[u8]) -> Result<T> {
+    match T::param_type() {
+        ParamType::Tuple(types) => T::from_token(Token::Tuple(decode(&types, data)?)),
+        ty => T::from_token(decode(&[ty], data)?.remove(0)),
+    }
+}
+
+/// Encode call with `selector` and arguments `value`.
+pub fn encode_call<T: AbiType>(selector: &[u8; 4], value: &T) -> Vec<u8> {
+    let mut res = selector.to_vec();
+    res.extend_from_slice(&encode_params(value));
+    res
+}
+
+/// Decode arguments of call `data`, `data` must start with `selector`.
+pub fn decode_call<T: AbiType>(selector: &[u8; 4], data: &[u8]) -> Result<T> {
+    match data.strip_prefix(selector.as_slice()) {
+        Some(data) => decode_params(data),
+        None => Err(Error::InvalidAbiData),
+    }
+}
+
+/// Decode indexed event field of static type from `topic`.
+pub fn topic<T: AbiType>(topic: Option<&[u8; 32]>) -> Result<T> {
+    let topic = topic.ok_or(Error::InvalidAbiData)?;
+    T::from_token(decode(&[T::param_type()], topic)?.remove(0))
+}
@@ -3,6 +3,19 @@ use core::fmt::Display;
 #[derive(Debug)]
 pub enum Error {
     UnknownProfileType,
+    UnknownProfile,
+    ProfileInheritsCycle,
+    InvalidAbiData,
+    InvalidAddress,
+    InvalidChecksum,
+    InvalidPrivateKey,
+    InvalidHex,
+    InvalidLinkReference,
+    UnknownLibrary,
+    UnlinkedLibrary,
+    UnknownNetwork,
+    InvalidAmount,
+    ConflictGasPolicy,
 }
 
 impl Display for Error {
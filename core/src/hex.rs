@@ -0,0 +1,37 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{Error, Result};
+
+/// Decode hex string with optional `0x` prefix.
+pub fn decode(s: &str) -> Result<Vec<u8>> {
+    let s = s.strip_prefix("0x").unwrap_or(s).as_bytes();
+
+    if s.len() % 2 != 0 {
+        return Err(Error::InvalidHex);
+    }
+
+    let digit = |b: u8| match b {
+        b'0'..=b'9' => Ok(b - b'0'),
+        b'a'..=b'f' => Ok(b - b'a' + 10),
+        b'A'..=b'F' => Ok(b - b'A' + 10),
+        _ => Err(Error::InvalidHex),
+    };
+
+    s.chunks(2)
+        .map(|c| Ok(digit(c[0])? << 4 | digit(c[1])?))
+        .collect()
+}
+
+/// Encode bytes as lowercase hex string without prefix.
+pub fn encode(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut res = String::with_capacity(bytes.len() * 2);
+
+    for b in bytes {
+        res.push(DIGITS[(b >> 4) as usize] as char);
+        res.push(DIGITS[(b & 0xf) as usize] as char);
+    }
+
+    res
+}
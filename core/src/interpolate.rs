@@ -0,0 +1,101 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Error of `interpolate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterpolateError {
+    /// Variable is not set and has no default.
+    Missing(String),
+    /// `${` without closing `}`.
+    Unterminated,
+}
+
+impl fmt::Display for InterpolateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(var) => write!(f, "environment variable `{var}` is not set"),
+            Self::Unterminated => write!(f, "unterminated `${{`"),
+        }
+    }
+}
+
+/// Replace `${VAR}` and `${VAR:-default}` in `s` with values from `lookup`.
+///
+/// Empty value is kept for `${VAR}`, default of `${VAR:-default}` is used
+/// when variable is unset or empty. `$${` is kept as `${`.
+pub fn interpolate<F>(s: &str, lookup: F) -> Result<String, InterpolateError>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut res = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(pos) = rest.find("${") {
+        if rest[..pos].ends_with('$') {
+            res.push_str(&rest[..pos - 1]);
+            res.push_str("${");
+            rest = &rest[pos + 2..];
+            continue;
+        }
+
+        res.push_str(&rest[..pos]);
+
+        let end = rest[pos..]
+            .find('}')
+            .ok_or(InterpolateError::Unterminated)?
+            + pos;
+        let expr = &rest[pos + 2..end];
+
+        let (var, default) = match expr.split_once(":-") {
+            Some((var, default)) => (var, Some(default)),
+            None => (expr, None),
+        };
+
+        let value = match default {
+            Some(_) => lookup(var).filter(|v| !v.is_empty()),
+            None => lookup(var),
+        };
+
+        match (value, default) {
+            (Some(value), _) => res.push_str(&value),
+            (None, Some(default)) => res.push_str(default),
+            (None, None) => return Err(InterpolateError::Missing(String::from(var))),
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    res.push_str(rest);
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::{String, ToString};
+
+    use super::{interpolate, InterpolateError};
+
+    #[test]
+    fn test_interpolate() {
+        let lookup = |var: &str| match var {
+            "KEY" => Some("abc".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        };
+
+        assert_eq!(interpolate("a/${KEY}/b", lookup).unwrap(), "a/abc/b");
+        assert_eq!(interpolate("${UNSET:-0.8.17}", lookup).unwrap(), "0.8.17");
+        assert_eq!(interpolate("${EMPTY:-x}", lookup).unwrap(), "x");
+        assert_eq!(interpolate("a${EMPTY}b", lookup).unwrap(), "ab");
+        assert_eq!(interpolate("$${KEY}", lookup).unwrap(), "${KEY}");
+        assert_eq!(
+            interpolate("${UNSET}", lookup).unwrap_err(),
+            InterpolateError::Missing("UNSET".to_string())
+        );
+        assert_eq!(
+            interpolate("${KEY", lookup).unwrap_err(),
+            InterpolateError::Unterminated
+        );
+    }
+}
@@ -2,11 +2,37 @@
 
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod snapper_file;
 pub use snapper_file::*;
 
+mod network;
+pub use network::*;
+
 mod profile;
 pub use profile::*;
 
+pub mod abi;
+
+pub mod hex;
+
+pub mod link;
+
+mod interpolate;
+pub use interpolate::*;
+
+#[cfg(feature = "std")]
+mod load;
+#[cfg(feature = "std")]
+pub use load::*;
+
+mod types;
+pub use types::*;
+
+mod validate;
+pub use validate::*;
+
 mod error;
 pub use error::*;
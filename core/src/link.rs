@@ -0,0 +1,104 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{hex, Address, Error, Result};
+
+/// Position of a library address in bytecode, in bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkReference {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// Link references keyed by source file then library name.
+pub type LinkReferences = BTreeMap<String, BTreeMap<String, Vec<LinkReference>>>;
+
+/// Write `address` into hex `code` at each byte offset of `positions`,
+/// replacing library placeholders. Mixed case `address` must have valid
+/// EIP-55 checksum.
+pub fn link_code(code: &mut String, positions: &[LinkReference], address: &str) -> Result<()> {
+    let address = hex::encode(&address.parse::<Address>()?.0);
+
+    for pos in positions {
+        let start = pos.start * 2;
+        let end = start + pos.length * 2;
+
+        if pos.length != 20 || end > code.len() || !code.is_char_boundary(start) {
+            return Err(Error::InvalidLinkReference);
+        }
+
+        code.replace_range(start..end, &address);
+    }
+
+    Ok(())
+}
+
+/// Bytecode with library placeholders, linked at deploy time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlinkedBytecode {
+    /// Hex string with `__$<hash>$__` placeholders.
+    pub object: String,
+    #[serde(rename = "linkReferences")]
+    pub link_references: LinkReferences,
+}
+
+impl UnlinkedBytecode {
+    /// Link library `lib` defined in `file` at `address`.
+    pub fn link(&mut self, file: &str, lib: &str, address: &str) -> Result<()> {
+        let libs = self
+            .link_references
+            .get_mut(file)
+            .ok_or(Error::UnknownLibrary)?;
+        let positions = libs.get(lib).ok_or(Error::UnknownLibrary)?;
+
+        link_code(&mut self.object, positions, address)?;
+
+        libs.remove(lib);
+        if libs.is_empty() {
+            self.link_references.remove(file);
+        }
+
+        Ok(())
+    }
+
+    /// Link all libraries referenced by bytecode in `libraries`, keyed by
+    /// file then library name, like `[library]` of `Snapper.toml`.
+    pub fn link_all(
+        &mut self,
+        libraries: &BTreeMap<String, BTreeMap<String, Address>>,
+    ) -> Result<()> {
+        for (file, libs) in libraries {
+            for (lib, address) in libs {
+                let referenced = self
+                    .link_references
+                    .get(file)
+                    .map(|l| l.contains_key(lib))
+                    .unwrap_or_default();
+
+                if referenced {
+                    self.link(file, lib, &address.to_string())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_linked(&self) -> bool {
+        self.link_references.is_empty()
+    }
+
+    /// Bytes of bytecode, all libraries must be linked.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        if !self.is_linked() {
+            return Err(Error::UnlinkedLibrary);
+        }
+
+        hex::decode(&self.object)
+    }
+}
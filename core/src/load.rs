@@ -0,0 +1,212 @@
+//! Loading `Snapper.toml` from disk, with `std` feature.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    env, fmt, format, fs, io,
+    path::Path,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{interpolate, toml_key, InterpolateError, SnapperFile, ValidationError};
+
+/// Error of loading `Snapper.toml`.
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    Dotenv(dotenvy::Error),
+    Toml(toml::de::Error),
+    /// Variable in string at TOML `path` can't be interpolated.
+    Interpolation {
+        path: String,
+        error: InterpolateError,
+    },
+    Invalid(Vec<ValidationError>),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Dotenv(e) => write!(f, "{e}"),
+            Self::Toml(e) => write!(f, "{e}"),
+            Self::Interpolation { path, error } => write!(f, "{error}, referenced by `{path}`"),
+            Self::Invalid(errors) => {
+                write!(f, "Invalid Snapper.toml:")?;
+
+                for e in errors {
+                    write!(f, "\n    {e}")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<io::Error> for LoadError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<dotenvy::Error> for LoadError {
+    fn from(e: dotenvy::Error) -> Self {
+        Self::Dotenv(e)
+    }
+}
+
+impl From<toml::de::Error> for LoadError {
+    fn from(e: toml::de::Error) -> Self {
+        Self::Toml(e)
+    }
+}
+
+/// Parse and validate `Snapper.toml`, `${VAR}` in strings is replaced with
+/// environment variable.
+pub fn load_snapper_file(s: &str) -> Result<SnapperFile, LoadError> {
+    load_snapper_file_with(s, |var| env::var(var).ok())
+}
+
+/// Load `Snapper.toml` at `path`, variables are also read from `.env` next
+/// to it. Environment variables take precedence over `.env`.
+pub fn load_snapper_path(path: &Path) -> Result<SnapperFile, LoadError> {
+    Ok(load_snapper_path_vars(path)?.0)
+}
+
+/// Same as `load_snapper_path`, with names of all interpolated variables,
+/// including unset ones.
+pub fn load_snapper_path_vars(path: &Path) -> Result<(SnapperFile, BTreeSet<String>), LoadError> {
+    let content = fs::read_to_string(path)?;
+
+    let mut dotenv = BTreeMap::new();
+
+    let dotenv_path = path.with_file_name(".env");
+    if dotenv_path.is_file() {
+        for item in dotenvy::from_path_iter(&dotenv_path)? {
+            let (key, value) = item?;
+            dotenv.insert(key, value);
+        }
+    }
+
+    let vars = RefCell::new(BTreeSet::new());
+
+    let snapper = load_snapper_file_with(&content, |var| {
+        vars.borrow_mut().insert(var.to_string());
+        env::var(var).ok().or_else(|| dotenv.get(var).cloned())
+    })?;
+
+    Ok((snapper, vars.into_inner()))
+}
+
+fn load_snapper_file_with<F>(s: &str, lookup: F) -> Result<SnapperFile, LoadError>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut value: toml::Value = toml::from_str(s)?;
+    interpolate_value(&mut value, "", &lookup)?;
+
+    let snapper: SnapperFile = value.try_into()?;
+
+    snapper.validate().map_err(LoadError::Invalid)?;
+
+    Ok(snapper)
+}
+
+/// Interpolate all strings in `value` at TOML `path`.
+fn interpolate_value<F>(value: &mut toml::Value, path: &str, lookup: &F) -> Result<(), LoadError>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    match value {
+        toml::Value::String(s) => {
+            *s = interpolate(s, lookup).map_err(|error| LoadError::Interpolation {
+                path: path.to_string(),
+                error,
+            })?;
+        }
+        toml::Value::Array(values) => {
+            for (i, v) in values.iter_mut().enumerate() {
+                interpolate_value(v, &format!("{path}[{i}]"), lookup)?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for (key, v) in table.iter_mut() {
+                let path = if path.is_empty() {
+                    toml_key(key)
+                } else {
+                    format!("{path}.{}", toml_key(key))
+                };
+
+                interpolate_value(v, &path, lookup)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, string::ToString, vec, vec::Vec};
+
+    use super::{load_snapper_file_with, load_snapper_path_vars};
+    use crate::Account;
+
+    #[test]
+    fn test_interpolate() {
+        let snapper = r#"
+            [project]
+
+            [solidity]
+            version = "${SOLC_VERSION:-0.8.17}"
+
+            [networks.goerli]
+            url = "https://goerli.infura.io/v3/${INFURA_KEY}"
+            accounts = ["${DEPLOYER_KEY}"]
+        "#;
+
+        let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+        let lookup = |var: &str| match var {
+            "INFURA_KEY" => Some("abc".to_string()),
+            "DEPLOYER_KEY" => Some(key.to_string()),
+            _ => None,
+        };
+
+        let sf = load_snapper_file_with(snapper, lookup).unwrap();
+        assert_eq!(sf.solidity.version, "0.8.17");
+        assert_eq!(sf.networks["goerli"].url, "https://goerli.infura.io/v3/abc");
+        assert_eq!(
+            sf.networks["goerli"].accounts,
+            vec![Account::PrivateKey(key.parse().unwrap())]
+        );
+
+        let err = load_snapper_file_with(snapper, |_| None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "environment variable `DEPLOYER_KEY` is not set, \
+             referenced by `networks.goerli.accounts[0]`"
+        );
+
+        let dir = std::env::temp_dir().join("snapper-vars-test");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Snapper.toml"), snapper).unwrap();
+        fs::write(
+            dir.join(".env"),
+            std::format!("INFURA_KEY=abc\nDEPLOYER_KEY={key}\n"),
+        )
+        .unwrap();
+
+        let (sf, vars) = load_snapper_path_vars(&dir.join("Snapper.toml")).unwrap();
+        assert_eq!(sf.networks["goerli"].url, "https://goerli.infura.io/v3/abc");
+        assert_eq!(
+            vars.into_iter().collect::<Vec<_>>(),
+            ["DEPLOYER_KEY", "INFURA_KEY", "SOLC_VERSION"]
+        );
+    }
+}
@@ -0,0 +1,327 @@
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use serde::{
+    de::{self, IntoDeserializer},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{Address, Error, PrivateKey, Result};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Network {
+    pub url: String,
+    pub accounts: Vec<Account>,
+    /// Library addresses on this network, override `[library]`.
+    #[serde(default)]
+    pub library: BTreeMap<String, BTreeMap<String, Address>>,
+    /// Expected chain id, checked against `eth_chainId` on connect.
+    pub chain_id: Option<u64>,
+    /// Legacy gas price, conflicts with `max_fee` and `priority_fee`.
+    pub gas_price: Option<Wei>,
+    /// EIP-1559 max fee per gas, estimated by node if unset.
+    pub max_fee: Option<Wei>,
+    /// EIP-1559 max priority fee per gas, estimated by node if unset.
+    pub priority_fee: Option<Wei>,
+    /// Multiplier applied on estimated gas limit.
+    #[serde(default = "default_gas_multiplier")]
+    pub gas_multiplier: f64,
+    /// Blocks to wait after transaction is mined.
+    #[serde(default = "default_confirmations")]
+    pub confirmations: u64,
+    /// Timeout of requests and waiting transactions, in seconds.
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+    /// Block explorer, like `https://goerli.etherscan.io`.
+    pub explorer_url: Option<String>,
+    pub explorer_api_key: Option<String>,
+}
+
+/// Account source of network, turned into signers by snapper runtime.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Account {
+    /// Private key, 32 bytes hex.
+    PrivateKey(PrivateKey),
+    /// BIP-39 mnemonic, derive `count` accounts at `<path>/<index>`.
+    Mnemonic {
+        mnemonic: String,
+        path: String,
+        index: u32,
+        count: u32,
+    },
+    /// Encrypted JSON keystore, relative to `Snapper.toml`. Password is read
+    /// from environment variable `password_env`.
+    Keystore {
+        keystore: String,
+        password_env: String,
+    },
+    /// JSON-RPC signer, sign with `eth_sign` and `eth_signTransaction`.
+    /// All accounts returned by `eth_accounts` are used without `address`.
+    Remote {
+        remote: String,
+        address: Option<Address>,
+    },
+}
+
+/// `Account` as written, keys and addresses are parsed after the variant is
+/// known, since untagged errors don't tell which field is invalid.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawAccount {
+    PrivateKey(String),
+    Mnemonic {
+        mnemonic: String,
+        #[serde(default = "default_derivation_path")]
+        path: String,
+        #[serde(default)]
+        index: u32,
+        #[serde(default = "default_count")]
+        count: u32,
+    },
+    Keystore {
+        keystore: String,
+        password_env: String,
+    },
+    Remote {
+        remote: String,
+        address: Option<String>,
+    },
+}
+
+impl<'de> Deserialize<'de> for Account {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> core::result::Result<Self, D::Error> {
+        let res = match RawAccount::deserialize(d)? {
+            RawAccount::PrivateKey(key) => {
+                Self::PrivateKey(PrivateKey::deserialize(key.into_deserializer())?)
+            }
+            RawAccount::Mnemonic {
+                mnemonic,
+                path,
+                index,
+                count,
+            } => Self::Mnemonic {
+                mnemonic,
+                path,
+                index,
+                count,
+            },
+            RawAccount::Keystore {
+                keystore,
+                password_env,
+            } => Self::Keystore {
+                keystore,
+                password_env,
+            },
+            RawAccount::Remote { remote, address } => Self::Remote {
+                remote,
+                address: address
+                    .map(|a| Address::deserialize(a.into_deserializer()))
+                    .transpose()?,
+            },
+        };
+
+        Ok(res)
+    }
+}
+
+fn default_derivation_path() -> String {
+    String::from("m/44'/60'/0'/0")
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+fn default_gas_multiplier() -> f64 {
+    1.0
+}
+
+fn default_confirmations() -> u64 {
+    1
+}
+
+fn default_timeout() -> u64 {
+    120
+}
+
+/// How gas price of transactions is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasPolicy {
+    /// Legacy transaction with fixed gas price.
+    Legacy { gas_price: Wei },
+    /// EIP-1559 transaction, unset fees are estimated by node.
+    Eip1559 {
+        max_fee: Option<Wei>,
+        priority_fee: Option<Wei>,
+    },
+}
+
+impl Network {
+    pub fn gas_policy(&self) -> Result<GasPolicy> {
+        match (self.gas_price, self.max_fee, self.priority_fee) {
+            (Some(gas_price), None, None) => Ok(GasPolicy::Legacy { gas_price }),
+            (Some(_), _, _) => Err(Error::ConflictGasPolicy),
+            (None, max_fee, priority_fee) => Ok(GasPolicy::Eip1559 {
+                max_fee,
+                priority_fee,
+            }),
+        }
+    }
+
+    /// Gas limit of transaction with `estimate` gas, rounded.
+    pub fn gas_limit(&self, estimate: u64) -> u64 {
+        (estimate as f64 * self.gas_multiplier + 0.5) as u64
+    }
+
+    /// Explorer page of transaction.
+    pub fn explorer_tx_url(&self, hash: &str) -> Option<String> {
+        let url = self.explorer_url.as_ref()?;
+        Some(format!("{}/tx/{hash}", url.trim_end_matches('/')))
+    }
+
+    /// Explorer page of address.
+    pub fn explorer_address_url(&self, address: &str) -> Option<String> {
+        let url = self.explorer_url.as_ref()?;
+        Some(format!("{}/address/{address}", url.trim_end_matches('/')))
+    }
+}
+
+/// Amount in wei, written as integer or string with unit, like `"2 gwei"`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Wei(pub u128);
+
+impl FromStr for Wei {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+
+        let (number, unit) = match s.find(|c: char| c.is_ascii_alphabetic()) {
+            Some(pos) => (s[..pos].trim(), s[pos..].trim()),
+            None => (s, "wei"),
+        };
+
+        let decimals = match unit.to_ascii_lowercase().as_str() {
+            "wei" => 0,
+            "gwei" => 9,
+            "ether" | "eth" => 18,
+            _ => return Err(Error::InvalidAmount),
+        };
+
+        let (int, frac) = number.split_once('.').unwrap_or((number, ""));
+
+        if int.is_empty() && frac.is_empty() || frac.len() > decimals {
+            return Err(Error::InvalidAmount);
+        }
+
+        let digits = format!("{int}{frac:0<decimals$}");
+
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::InvalidAmount);
+        }
+
+        digits.parse().map(Self).map_err(|_| Error::InvalidAmount)
+    }
+}
+
+impl fmt::Display for Wei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} wei", self.0)
+    }
+}
+
+impl Serialize for Wei {
+    fn serialize<S: Serializer>(&self, s: S) -> core::result::Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Wei {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> core::result::Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Wei;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("amount in wei or string like \"2 gwei\"")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> core::result::Result<Wei, E> {
+                Ok(Wei(v.into()))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> core::result::Result<Wei, E> {
+                u128::try_from(v)
+                    .map(Wei)
+                    .map_err(|_| E::custom("negative amount"))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> core::result::Result<Wei, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        d.deserialize_any(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{validate::test::errors, GasPolicy, SnapperFile, Wei};
+
+    #[test]
+    fn test_network() {
+        let snapper = r#"
+            [project]
+
+            [solidity]
+            version = "0.8.17"
+
+            [networks.goerli]
+            url = "https://goerli.example.com"
+            accounts = []
+            chain_id = 5
+            max_fee = "30 gwei"
+            priority_fee = "1.5 gwei"
+            gas_multiplier = 1.2
+            explorer_url = "https://goerli.etherscan.io/"
+
+            [networks.bad]
+            url = "http://127.0.0.1:8545/"
+            accounts = []
+            gas_price = 1000000000
+            max_fee = "2 gwei"
+            gas_multiplier = 0
+        "#;
+
+        let sf: SnapperFile = toml::from_str(snapper).unwrap();
+        let err = errors(&sf);
+
+        assert!(err.contains("`networks.bad.gas_price`: conflicts with"));
+        assert!(err.contains("`networks.bad.gas_multiplier`: must be positive"));
+        assert!(!err.contains("goerli"));
+
+        let snapper = snapper.split("[networks.bad]").next().unwrap();
+        let snapper_file: SnapperFile = toml::from_str(snapper).unwrap();
+        snapper_file.validate().unwrap();
+        let goerli = &snapper_file.networks["goerli"];
+
+        assert_eq!(goerli.chain_id, Some(5));
+        assert_eq!(
+            goerli.gas_policy().unwrap(),
+            GasPolicy::Eip1559 {
+                max_fee: Some(Wei(30_000_000_000)),
+                priority_fee: Some(Wei(1_500_000_000)),
+            }
+        );
+        assert_eq!(goerli.gas_limit(100_000), 120_000);
+        assert_eq!(goerli.confirmations, 1);
+        assert_eq!(goerli.timeout, 120);
+        assert_eq!(
+            goerli.explorer_tx_url("0x01").unwrap(),
+            "https://goerli.etherscan.io/tx/0x01"
+        );
+    }
+}
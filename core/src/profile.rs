@@ -1,12 +1,29 @@
+use alloc::string::{String, ToString};
 use core::str::FromStr;
 
 use crate::{Error, Result};
 
-/// Profile
-#[derive(Debug, Clone)]
+/// Profile to compile with, from cargo profile name.
+///
+/// Names other than `debug` and `release` are profiles in
+/// `[solidity.profiles]`, `dev`, `test` and `bench` fall back to built in
+/// profiles when not configured.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ProfileType {
     Debug,
     Release,
+    Named(String),
+}
+
+impl ProfileType {
+    /// Name in `[solidity.profiles]`.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Debug => "debug",
+            Self::Release => "release",
+            Self::Named(name) => name,
+        }
+    }
 }
 
 impl FromStr for ProfileType {
@@ -16,7 +33,8 @@ impl FromStr for ProfileType {
         match ty {
             "release" => Ok(Self::Release),
             "debug" => Ok(Self::Debug),
-            _ => Err(Error::UnknownProfileType),
+            "" => Err(Error::UnknownProfileType),
+            _ => Ok(Self::Named(ty.to_string())),
         }
     }
 }
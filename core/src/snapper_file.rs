@@ -1,24 +1,84 @@
-use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
 
 use serde::{Deserialize, Serialize};
 
-use crate::ProfileType;
+use crate::{Address, Error, Network, ProfileType, Result};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SnapperFile {
     pub project: Project,
     pub solidity: Solidity,
     #[serde(default)]
-    pub library: BTreeMap<String, BTreeMap<String, String>>,
+    pub library: BTreeMap<String, BTreeMap<String, Address>>,
     pub networks: BTreeMap<String, Network>,
 }
 
 impl SnapperFile {
-    pub fn get_solidity_profile(&self, profile: &ProfileType) -> &Profile {
-        match profile {
-            ProfileType::Debug => &self.solidity.profiles.debug,
-            ProfileType::Release => &self.solidity.profiles.release,
+    /// Profile resolved with `inherits`, see `Profiles::get`.
+    pub fn get_solidity_profile(&self, profile: &ProfileType) -> Result<Profile> {
+        self.solidity.profiles.get(profile.name())
+    }
+
+    /// Library addresses used on `network`, keyed by file then library name.
+    ///
+    /// `[networks.<name>.library]` overrides `[library]`, only `[library]`
+    /// is used without network.
+    pub fn libraries(
+        &self,
+        network: Option<&str>,
+    ) -> Result<BTreeMap<String, BTreeMap<String, Address>>> {
+        let mut res = self.library.clone();
+
+        if let Some(name) = network {
+            let network = self.networks.get(name).ok_or(Error::UnknownNetwork)?;
+
+            for (file, libs) in &network.library {
+                let entry = res.entry(file.clone()).or_default();
+
+                for (lib, address) in libs {
+                    entry.insert(lib.clone(), *address);
+                }
+            }
         }
+
+        Ok(res)
+    }
+
+    /// Apply compiler configuration on `[solidity]`.
+    ///
+    /// Result has no extra compilers and overrides.
+    pub fn with_compiler(&self, compiler: &Compiler) -> Self {
+        let mut res = self.clone();
+        let solidity = &mut res.solidity;
+
+        if let Some(version) = &compiler.version {
+            solidity.version = version.clone();
+        }
+
+        if let Some(via_ir) = compiler.via_ir {
+            solidity.via_ir = via_ir;
+        }
+
+        if let Some(evm_version) = compiler.evm_version {
+            solidity.evm_version = Some(evm_version);
+        }
+
+        if let Some(optimizer) = &compiler.optimizer {
+            let profiles = &mut solidity.profiles.0;
+
+            for name in ["debug", "release"] {
+                profiles.entry(String::from(name)).or_default();
+            }
+
+            for profile in profiles.values_mut() {
+                profile.optimizer.merge(optimizer);
+            }
+        }
+
+        solidity.compilers.clear();
+        solidity.overrides.clear();
+
+        res
     }
 }
 
@@ -27,26 +87,118 @@ pub struct Project {
     pub rename: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Profiles {
-    pub debug: Profile,
-    pub release: Profile,
+/// Profiles in `[solidity.profiles.<name>]`, keyed by name.
+///
+/// `debug` and `release` are built in, and used by cargo profiles `dev`,
+/// `test` and `bench` unless configured. Other profiles inherit `debug`
+/// without `inherits`.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(transparent)]
+pub struct Profiles(pub BTreeMap<String, ProfileOverride>);
+
+/// Max depth of `inherits`, deeper chain is a cycle.
+const MAX_INHERITS: usize = 32;
+
+impl Profiles {
+    /// Resolve profile `name` with `inherits`.
+    pub fn get(&self, name: &str) -> Result<Profile> {
+        self.resolve(name, 0)
+    }
+
+    fn resolve(&self, name: &str, depth: usize) -> Result<Profile> {
+        if depth > MAX_INHERITS {
+            return Err(Error::ProfileInheritsCycle);
+        }
+
+        // Like cargo, `dev` and `test` are based on `debug`, `bench` on
+        // `release`, including their configuration.
+        let base = match name {
+            "dev" | "test" => Some("debug"),
+            "bench" => Some("release"),
+            _ => None,
+        };
+
+        let builtin = match name {
+            "debug" => Some(Profile::debug()),
+            "release" => Some(Profile::release()),
+            _ => None,
+        };
+
+        let Some(config) = self.0.get(name) else {
+            return match base {
+                Some(base) => self.resolve(base, depth + 1),
+                None => builtin.ok_or(Error::UnknownProfile),
+            };
+        };
+
+        let mut profile = match (config.inherits.as_deref().or(base), builtin) {
+            (Some(parent), _) if parent != name => self.resolve(parent, depth + 1)?,
+            (Some(_), _) => return Err(Error::ProfileInheritsCycle),
+            (None, Some(builtin)) => builtin,
+            (None, None) => Profile::debug(),
+        };
+
+        if let Some(debug) = config.debug {
+            profile.debug = debug;
+            profile.revert_strings = if debug {
+                RevertStrings::Debug
+            } else {
+                RevertStrings::Default
+            };
+        }
+
+        if let Some(revert_strings) = &config.revert_strings {
+            profile.revert_strings = revert_strings.clone();
+        }
+
+        if let Some(debug_info) = &config.debug_info {
+            profile.debug_info = debug_info.clone();
+        }
+
+        profile.optimizer.apply(&config.optimizer);
+        profile.metadata.merge(&config.metadata);
+
+        Ok(profile)
+    }
+}
+
+/// Profile configuration, unset fields inherit from `inherits`.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ProfileOverride {
+    pub inherits: Option<String>,
+    /// Debug build, also set `revert_strings` to `debug` or `default`.
+    pub debug: Option<bool>,
+    pub revert_strings: Option<RevertStrings>,
+    pub debug_info: Option<Vec<DebugInfo>>,
+    pub optimizer: OptimizerOverride,
+    pub metadata: Metadata,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Profile {
     pub debug: bool,
+    pub revert_strings: RevertStrings,
+    /// Debug info in generated code, like `["location", "snippet"]`.
+    pub debug_info: Vec<DebugInfo>,
     pub optimizer: Optimizer,
+    pub metadata: Metadata,
 }
 
-impl Default for Profiles {
-    fn default() -> Self {
-        let debug = Profile {
+impl Profile {
+    pub fn debug() -> Self {
+        Self {
             debug: true,
+            revert_strings: RevertStrings::Debug,
+            debug_info: vec![DebugInfo::All],
+            metadata: Metadata::default(),
             optimizer: Optimizer {
                 enable: false,
                 runs: 0,
+                peephole: true,
+                order_literals: false,
                 yul: false,
+                yul_steps: None,
                 yul_stack: false,
                 inliner: false,
                 deduplicate: false,
@@ -54,14 +206,22 @@ impl Default for Profiles {
                 remove_jumpdest: false,
                 cse: false,
             },
-        };
+        }
+    }
 
-        let release = Profile {
+    pub fn release() -> Self {
+        Self {
             debug: false,
+            revert_strings: RevertStrings::Default,
+            debug_info: vec![DebugInfo::All],
+            metadata: Metadata::default(),
             optimizer: Optimizer {
                 enable: true,
                 runs: 300,
+                peephole: true,
+                order_literals: false,
                 yul: true,
+                yul_steps: None,
                 yul_stack: true,
                 inliner: true,
                 deduplicate: true,
@@ -69,13 +229,77 @@ impl Default for Profiles {
                 remove_jumpdest: true,
                 cse: true,
             },
-        };
+        }
+    }
+}
 
-        Self { debug, release }
+/// How revert reason strings are kept.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum RevertStrings {
+    #[serde(rename = "default")]
+    #[default]
+    Default,
+    #[serde(rename = "strip")]
+    Strip,
+    #[serde(rename = "debug")]
+    Debug,
+    #[serde(rename = "verboseDebug")]
+    VerboseDebug,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum DebugInfo {
+    #[serde(rename = "location")]
+    Location,
+    #[serde(rename = "snippet")]
+    Snippet,
+    #[serde(rename = "*")]
+    All,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum BytecodeHash {
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "ipfs")]
+    Ipfs,
+    #[serde(rename = "bzzr1")]
+    Bzzr1,
+}
+
+/// Metadata settings, unset fields use solc defaults.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct Metadata {
+    pub bytecode_hash: Option<BytecodeHash>,
+    /// Append CBOR metadata to bytecode, solc 0.8.18 and later.
+    pub append_cbor: Option<bool>,
+    pub use_literal_content: Option<bool>,
+}
+
+impl Metadata {
+    /// Set fields of `other` on `self`.
+    pub fn merge(&mut self, other: &Self) {
+        if other.bytecode_hash.is_some() {
+            self.bytecode_hash = other.bytecode_hash.clone();
+        }
+
+        if other.append_cbor.is_some() {
+            self.append_cbor = other.append_cbor;
+        }
+
+        if other.use_literal_content.is_some() {
+            self.use_literal_content = other.use_literal_content;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
     }
 }
 
-#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+/// EVM hardfork to target, in order of activation.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum EvmVersion {
     #[serde(rename = "homestead")]
     Homestead,
@@ -84,7 +308,6 @@ pub enum EvmVersion {
     #[serde(rename = "spuriousDragon")]
     SpuriousDragon,
     #[serde(rename = "byzantium")]
-    #[default]
     Byzantium,
     #[serde(rename = "constantinople")]
     Constantinople,
@@ -98,6 +321,84 @@ pub enum EvmVersion {
     London,
     #[serde(rename = "paris")]
     Paris,
+    #[serde(rename = "shanghai")]
+    Shanghai,
+    #[serde(rename = "cancun")]
+    Cancun,
+    #[serde(rename = "prague")]
+    Prague,
+}
+
+/// Solc version as `(major, minor, patch)`.
+pub type SolcVersion = (u64, u64, u64);
+
+impl EvmVersion {
+    /// Name used by solc.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Homestead => "homestead",
+            Self::TangerineWhistle => "tangerineWhistle",
+            Self::SpuriousDragon => "spuriousDragon",
+            Self::Byzantium => "byzantium",
+            Self::Constantinople => "constantinople",
+            Self::Petersburg => "petersburg",
+            Self::Istanbul => "istanbul",
+            Self::Berlin => "berlin",
+            Self::London => "london",
+            Self::Paris => "paris",
+            Self::Shanghai => "shanghai",
+            Self::Cancun => "cancun",
+            Self::Prague => "prague",
+        }
+    }
+
+    /// First solc version supporting this EVM version.
+    pub fn min_solc(&self) -> SolcVersion {
+        match self {
+            Self::Homestead
+            | Self::TangerineWhistle
+            | Self::SpuriousDragon
+            | Self::Byzantium
+            | Self::Constantinople => (0, 4, 21),
+            Self::Petersburg => (0, 5, 5),
+            Self::Istanbul => (0, 5, 13),
+            Self::Berlin => (0, 8, 5),
+            Self::London => (0, 8, 7),
+            Self::Paris => (0, 8, 18),
+            Self::Shanghai => (0, 8, 20),
+            Self::Cancun => (0, 8, 24),
+            Self::Prague => (0, 8, 27),
+        }
+    }
+
+    pub fn is_supported_by(&self, solc: SolcVersion) -> bool {
+        solc >= self.min_solc()
+    }
+
+    /// Default EVM version of `solc`, `None` before solc 0.4.21 which has
+    /// no `evmVersion` setting.
+    pub fn default_for(solc: SolcVersion) -> Option<Self> {
+        let res = match solc {
+            v if v >= (0, 8, 30) => Self::Prague,
+            v if v >= (0, 8, 25) => Self::Cancun,
+            v if v >= (0, 8, 20) => Self::Shanghai,
+            v if v >= (0, 8, 18) => Self::Paris,
+            v if v >= (0, 8, 7) => Self::London,
+            v if v >= (0, 8, 5) => Self::Berlin,
+            v if v >= (0, 5, 14) => Self::Istanbul,
+            v if v >= (0, 5, 5) => Self::Petersburg,
+            v if v >= (0, 4, 21) => Self::Byzantium,
+            _ => return None,
+        };
+
+        Some(res)
+    }
+}
+
+impl core::fmt::Display for EvmVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -105,17 +406,154 @@ pub struct Solidity {
     pub version: String,
     #[serde(default)]
     pub via_ir: bool,
-    #[serde(default)]
-    pub evm_version: EvmVersion,
+    /// Default is the default EVM version of selected solc.
+    pub evm_version: Option<EvmVersion>,
     #[serde(default)]
     pub profiles: Profiles,
+    /// Import remappings, `prefix = "target"` or `"context:prefix" = "target"`.
+    #[serde(default)]
+    pub remappings: BTreeMap<String, String>,
+    /// Extra directories to search for imported files.
+    #[serde(default)]
+    pub include_paths: Vec<String>,
+    /// Root of source unit names, default is contract directory.
+    pub base_path: Option<String>,
+    /// Never access network, also enabled by `SNAPPER_OFFLINE`.
+    #[serde(default)]
+    pub offline: bool,
+    /// Url of version registry, `file://` is supported.
+    /// Registry bundled in snapper is used by default.
+    pub upstream: Option<String>,
+    /// Use this solc binary instead of downloading one.
+    pub solc_path: Option<String>,
+    /// Extra compilers, each source uses the first compiler compatible
+    /// with its pragma, start from `[solidity]` itself.
+    #[serde(default)]
+    pub compilers: Vec<Compiler>,
+    /// Compiler configuration for source unit, like `"legacy/Token.sol"`.
+    #[serde(default)]
+    pub overrides: BTreeMap<String, Compiler>,
+    /// Compiler outputs to write as artifacts.
+    #[serde(default)]
+    pub outputs: Outputs,
+}
+
+/// Compiler outputs written as artifact files of each contract.
+///
+/// Abi is always written, bindings are generated from it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Outputs {
+    /// `<Contract>.metadata.json`
+    pub metadata: bool,
+    /// `<Contract>.bytecode`, `<Contract>.opcodes` and `<Contract>.sourcemap`
+    pub bytecode: bool,
+    /// `<Contract>.deployed.bytecode`, `<Contract>.deployed.opcodes` and
+    /// `<Contract>.deployed.sourcemap`
+    pub deployed_bytecode: bool,
+    /// `<Contract>.gas.json`
+    pub gas_estimates: bool,
+    /// `<Contract>.methods.json`
+    pub method_identifiers: bool,
+    /// `<Contract>.storage.json`
+    pub storage_layout: bool,
+    /// `<Contract>.devdoc.json`
+    pub devdoc: bool,
+    /// `<Contract>.userdoc.json`
+    pub userdoc: bool,
+    /// `<Contract>.ir`
+    pub ir: bool,
+    /// `<Contract>.optimized.ir`
+    pub ir_optimized: bool,
+    /// `<Contract>.asm`
+    pub assembly: bool,
+}
+
+impl Default for Outputs {
+    fn default() -> Self {
+        Self {
+            metadata: true,
+            bytecode: true,
+            deployed_bytecode: true,
+            gas_estimates: true,
+            method_identifiers: false,
+            storage_layout: false,
+            devdoc: false,
+            userdoc: false,
+            ir: false,
+            ir_optimized: false,
+            assembly: false,
+        }
+    }
+}
+
+/// Compiler configuration, unset fields inherit from `[solidity]`.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct Compiler {
+    pub version: Option<String>,
+    pub via_ir: Option<bool>,
+    pub evm_version: Option<EvmVersion>,
+    pub optimizer: Option<OptimizerOverride>,
+}
+
+/// Optimizer fields override, unset fields are inherited.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct OptimizerOverride {
+    pub enable: Option<bool>,
+    pub runs: Option<u32>,
+    pub peephole: Option<bool>,
+    pub order_literals: Option<bool>,
+    pub yul: Option<bool>,
+    /// Yul optimizer steps, like `"dhfoDgvulfnTUtnIf"`.
+    pub yul_steps: Option<String>,
+    pub yul_stack: Option<bool>,
+    pub inliner: Option<bool>,
+    pub deduplicate: Option<bool>,
+    pub constant: Option<bool>,
+    pub remove_jumpdest: Option<bool>,
+    pub cse: Option<bool>,
+}
+
+impl OptimizerOverride {
+    /// Set fields of `other` on `self`.
+    pub fn merge(&mut self, other: &Self) {
+        macro_rules! merge {
+            ($($field:ident),*) => {
+                $(if other.$field.is_some() {
+                    self.$field = other.$field;
+                })*
+            };
+        }
+
+        merge!(
+            enable,
+            runs,
+            peephole,
+            order_literals,
+            yul,
+            yul_stack,
+            inliner,
+            deduplicate,
+            constant,
+            remove_jumpdest,
+            cse
+        );
+
+        if other.yul_steps.is_some() {
+            self.yul_steps = other.yul_steps.clone();
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Optimizer {
     pub enable: bool,
     pub runs: u32,
+    pub peephole: bool,
+    pub order_literals: bool,
     pub yul: bool,
+    pub yul_steps: Option<String>,
     pub yul_stack: bool,
     pub inliner: bool,
     pub deduplicate: bool,
@@ -124,8 +562,142 @@ pub struct Optimizer {
     pub cse: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Network {
-    pub url: String,
-    pub accounts: Vec<String>,
+impl Optimizer {
+    /// Apply set fields of `config`.
+    pub fn apply(&mut self, config: &OptimizerOverride) {
+        macro_rules! apply {
+            ($($field:ident),*) => {
+                $(if let Some(v) = config.$field {
+                    self.$field = v;
+                })*
+            };
+        }
+
+        apply!(
+            enable,
+            runs,
+            peephole,
+            order_literals,
+            yul,
+            yul_stack,
+            inliner,
+            deduplicate,
+            constant,
+            remove_jumpdest,
+            cse
+        );
+
+        if config.yul_steps.is_some() {
+            self.yul_steps = config.yul_steps.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{format, vec};
+
+    use crate::{
+        validate::test::errors, BytecodeHash, DebugInfo, ProfileType, RevertStrings, SnapperFile,
+    };
+
+    #[test]
+    fn test_profiles() {
+        let snapper = r#"
+            [project]
+
+            [networks]
+
+            [solidity]
+            version = "0.8.17"
+
+            [solidity.profiles.release]
+            optimizer = { runs = 1000 }
+
+            [solidity.profiles.ci]
+            inherits = "release"
+            debug = true
+            debug_info = ["location"]
+            optimizer = { peephole = false, yul_steps = "dhfoDgvulfnTUtnIf" }
+            metadata = { bytecode_hash = "none", append_cbor = false }
+
+            [solidity.profiles.prod]
+            inherits = "ci"
+            revert_strings = "strip"
+
+            [solidity.profiles.local]
+            optimizer = { enable = true }
+        "#;
+
+        let sf: SnapperFile = toml::from_str(snapper).unwrap();
+        sf.validate().unwrap();
+        let get = |name: &str| sf.get_solidity_profile(&name.parse().unwrap());
+
+        let release = get("release").unwrap();
+        assert!(!release.debug);
+        assert_eq!(release.optimizer.runs, 1000);
+        assert!(release.optimizer.yul);
+
+        let ci = get("ci").unwrap();
+        assert!(ci.debug);
+        assert_eq!(ci.revert_strings, RevertStrings::Debug);
+        assert_eq!(ci.debug_info, vec![DebugInfo::Location]);
+        assert_eq!(ci.optimizer.runs, 1000);
+        assert!(!ci.optimizer.peephole && ci.optimizer.yul);
+        assert_eq!(ci.optimizer.yul_steps.as_deref(), Some("dhfoDgvulfnTUtnIf"));
+        assert_eq!(ci.metadata.bytecode_hash, Some(BytecodeHash::None));
+        assert_eq!(ci.metadata.use_literal_content, None);
+
+        let prod = get("prod").unwrap();
+        assert_eq!(prod.revert_strings, RevertStrings::Strip);
+        assert_eq!(prod.metadata.append_cbor, Some(false));
+        assert!(release.metadata.is_empty());
+
+        assert_eq!(get("bench").unwrap().optimizer.runs, 1000);
+        assert!(get("test").unwrap().debug);
+        assert_eq!("test".parse::<ProfileType>().unwrap().name(), "test");
+
+        let mut configured = sf.clone();
+        let test = configured
+            .solidity
+            .profiles
+            .0
+            .entry("test".into())
+            .or_default();
+        test.revert_strings = Some(RevertStrings::Strip);
+        test.optimizer.runs = Some(7);
+        let bench = configured
+            .solidity
+            .profiles
+            .0
+            .entry("bench".into())
+            .or_default();
+        bench.debug = Some(true);
+
+        let test = configured
+            .get_solidity_profile(&"test".parse().unwrap())
+            .unwrap();
+        assert_eq!(test.revert_strings, RevertStrings::Strip);
+        assert_eq!(test.optimizer.runs, 7);
+        assert!(test.debug);
+
+        let bench = configured
+            .get_solidity_profile(&"bench".parse().unwrap())
+            .unwrap();
+        assert!(bench.debug && bench.optimizer.enable);
+        assert_eq!(bench.optimizer.runs, 1000);
+
+        let local = get("local").unwrap();
+        assert!(local.debug && local.optimizer.enable && !local.optimizer.yul);
+
+        assert!(get("unknown").is_err());
+
+        let sf: SnapperFile = toml::from_str(&format!(
+            "{snapper}\n[solidity.profiles.a]\ninherits = \"b\"\n\
+             [solidity.profiles.b]\ninherits = \"a\"\n"
+        ))
+        .unwrap();
+        let err = errors(&sf);
+        assert!(err.contains("`solidity.profiles.a.inherits`: cycle in `inherits`"));
+    }
 }
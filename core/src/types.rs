@@ -0,0 +1,195 @@
+use alloc::{string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::{hex, Error, Result};
+
+/// Keccak-256 hash of `data`.
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut res = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(data);
+    hasher.finalize(&mut res);
+    res
+}
+
+/// Implement serde as string with `Display` and `FromStr`.
+macro_rules! serde_str {
+    ($ty:ty) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, s: S) -> core::result::Result<S::Ok, S::Error> {
+                s.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(d: D) -> core::result::Result<Self, D::Error> {
+                let s = String::deserialize(d)?;
+                s.parse().map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+/// Ethereum address, displayed with EIP-55 checksum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Address(pub [u8; 20]);
+
+impl Address {
+    /// Address in EIP-55 mixed case checksum format.
+    pub fn to_checksum(&self) -> String {
+        let lower = hex::encode(&self.0);
+        let hash = keccak256(lower.as_bytes());
+
+        let mut res = String::with_capacity(42);
+        res.push_str("0x");
+
+        for (i, c) in lower.chars().enumerate() {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0xf;
+
+            if nibble >= 8 {
+                res.push(c.to_ascii_uppercase());
+            } else {
+                res.push(c);
+            }
+        }
+
+        res
+    }
+}
+
+impl FromStr for Address {
+    type Err = Error;
+
+    /// Parse `0x` prefixed address, mixed case address must have valid
+    /// EIP-55 checksum.
+    fn from_str(s: &str) -> Result<Self> {
+        let digits = s.strip_prefix("0x").ok_or(Error::InvalidAddress)?;
+
+        if digits.len() != 40 {
+            return Err(Error::InvalidAddress);
+        }
+
+        let bytes = hex::decode(digits).map_err(|_| Error::InvalidAddress)?;
+
+        let mut res = Self([0u8; 20]);
+        res.0.copy_from_slice(&bytes);
+
+        let lower = digits.bytes().all(|b| !b.is_ascii_uppercase());
+        let upper = digits.bytes().all(|b| !b.is_ascii_lowercase());
+
+        if !lower && !upper && res.to_checksum() != s {
+            return Err(Error::InvalidChecksum);
+        }
+
+        Ok(res)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_checksum())
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, s: S) -> core::result::Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    /// Parse as `FromStr`, with expected checksum in error.
+    fn deserialize<D: Deserializer<'de>>(d: D) -> core::result::Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+
+        s.parse().map_err(|e| match e {
+            Error::InvalidChecksum => match s.to_ascii_lowercase().parse::<Address>() {
+                Ok(a) => de::Error::custom(format_args!(
+                    "invalid EIP-55 checksum of `{s}`, expected `{a}`"
+                )),
+                Err(_) => de::Error::custom(format_args!("invalid address `{s}`")),
+            },
+            _ => de::Error::custom(format_args!(
+                "invalid address `{s}`, expected `0x` and 40 hex digits"
+            )),
+        })
+    }
+}
+
+/// Secp256k1 private key, never printed.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PrivateKey([u8; 32]);
+
+impl PrivateKey {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Key as `0x` prefixed hex, keep it secret.
+    pub fn to_hex(&self) -> String {
+        let mut res = String::from("0x");
+        res.push_str(&hex::encode(&self.0));
+        res
+    }
+}
+
+impl FromStr for PrivateKey {
+    type Err = Error;
+
+    /// Parse 32 bytes hex with optional `0x` prefix, zero is rejected.
+    fn from_str(s: &str) -> Result<Self> {
+        let bytes = hex::decode(s).map_err(|_| Error::InvalidPrivateKey)?;
+
+        if bytes.len() != 32 || bytes.iter().all(|b| *b == 0) {
+            return Err(Error::InvalidPrivateKey);
+        }
+
+        let mut res = Self([0u8; 32]);
+        res.0.copy_from_slice(&bytes);
+
+        Ok(res)
+    }
+}
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PrivateKey(..)")
+    }
+}
+
+impl Serialize for PrivateKey {
+    fn serialize<S: Serializer>(&self, s: S) -> core::result::Result<S::Ok, S::Error> {
+        s.serialize_str(&self.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for PrivateKey {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> core::result::Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+        s.parse()
+            .map_err(|_| de::Error::custom("invalid private key, expected 32 bytes hex"))
+    }
+}
+
+/// Bytes written as `0x` prefixed hex.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Bytes(pub Vec<u8>);
+
+impl FromStr for Bytes {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Self(hex::decode(s)?))
+    }
+}
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(&self.0))
+    }
+}
+
+serde_str!(Bytes);
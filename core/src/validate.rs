@@ -0,0 +1,226 @@
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+
+use crate::{Account, Error, SnapperFile};
+
+/// Invalid field of `Snapper.toml`.
+#[derive(Debug, Clone)]
+pub struct ValidationError {
+    /// TOML path of field, like `networks.goerli.accounts[0]`.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.path, self.message)
+    }
+}
+
+/// Quote `key` unless it is a bare TOML key.
+pub fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-');
+
+    if bare {
+        String::from(key)
+    } else {
+        format!("{key:?}")
+    }
+}
+
+fn check_account(errors: &mut Vec<ValidationError>, path: String, account: &Account) {
+    let mut error = |field: &str, message: &str| {
+        errors.push(ValidationError {
+            path: format!("{path}{field}"),
+            message: String::from(message),
+        })
+    };
+
+    match account {
+        Account::PrivateKey(_) => {}
+        Account::Mnemonic {
+            mnemonic,
+            path,
+            index,
+            count,
+        } => {
+            let words = mnemonic.split_whitespace().count();
+            if ![12, 15, 18, 21, 24].contains(&words) {
+                error(".mnemonic", "expected 12, 15, 18, 21 or 24 words");
+            }
+
+            if !path.starts_with("m/") {
+                error(".path", "derivation path must start with `m/`");
+            }
+
+            if *count == 0 {
+                error(".count", "must be positive");
+            } else if index.checked_add(*count).is_none() {
+                error(".count", "`index` plus `count` overflows");
+            }
+        }
+        Account::Keystore {
+            keystore,
+            password_env,
+        } => {
+            if keystore.is_empty() {
+                error(".keystore", "path is empty");
+            }
+
+            if password_env.is_empty() {
+                error(".password_env", "variable name is empty");
+            }
+        }
+        Account::Remote { remote, .. } => {
+            if remote.is_empty() {
+                error(".remote", "url is empty");
+            }
+        }
+    }
+}
+
+impl SnapperFile {
+    /// Check fields beyond parsing, like account sources and profile
+    /// inheritance, report every invalid field. Addresses and private keys
+    /// are checked when parsing.
+    pub fn validate(&self) -> core::result::Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        for name in self.solidity.profiles.0.keys() {
+            let message = match self.solidity.profiles.get(name) {
+                Ok(_) => continue,
+                Err(Error::ProfileInheritsCycle) => String::from("cycle in `inherits`"),
+                Err(_) => String::from("inherits unknown profile"),
+            };
+
+            errors.push(ValidationError {
+                path: format!("solidity.profiles.{}.inherits", toml_key(name)),
+                message,
+            });
+        }
+
+        for (name, network) in &self.networks {
+            let prefix = format!("networks.{}", toml_key(name));
+
+            if network.url.is_empty() {
+                errors.push(ValidationError {
+                    path: format!("{prefix}.url"),
+                    message: String::from("url is empty"),
+                });
+            }
+
+            for (i, account) in network.accounts.iter().enumerate() {
+                check_account(&mut errors, format!("{prefix}.accounts[{i}]"), account);
+            }
+
+            if network.gas_policy().is_err() {
+                errors.push(ValidationError {
+                    path: format!("{prefix}.gas_price"),
+                    message: String::from("conflicts with `max_fee` and `priority_fee`"),
+                });
+            }
+
+            if network.gas_multiplier.is_nan() || network.gas_multiplier <= 0.0 {
+                errors.push(ValidationError {
+                    path: format!("{prefix}.gas_multiplier"),
+                    message: String::from("must be positive"),
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use alloc::{string::ToString, vec::Vec};
+
+    use crate::SnapperFile;
+
+    /// All validation errors of `sf`, one per line.
+    pub(crate) fn errors(sf: &SnapperFile) -> alloc::string::String {
+        let errors: Vec<_> = sf
+            .validate()
+            .unwrap_err()
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        errors.join("\n")
+    }
+
+    #[test]
+    fn test_validate() {
+        let snapper = r#"
+            [project]
+
+            [solidity]
+            version = "0.8.17"
+
+            [library."Locker.sol"]
+            LockerLib = "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+
+            [networks.localhost]
+            url = "http://127.0.0.1:8545/"
+            accounts = [
+                "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+                { mnemonic = "test test junk", count = 0 },
+                { mnemonic = "test test test test test test test test test test test junk", index = 4294967295, count = 2 },
+                { keystore = "keys/deployer.json", password_env = "" },
+                { remote = "http://127.0.0.1:8550/", address = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8" },
+                { mnemonic = "test test test test test test test test test test test junk" },
+            ]
+        "#;
+
+        let sf: SnapperFile = toml::from_str(snapper).unwrap();
+        let err = errors(&sf);
+
+        assert!(!err.contains("accounts[0]"));
+        assert!(err.contains("`networks.localhost.accounts[1].mnemonic`: expected 12"));
+        assert!(err.contains("`networks.localhost.accounts[1].count`: must be positive"));
+        assert!(err.contains("`networks.localhost.accounts[2].count`: `index` plus `count`"));
+        assert!(err.contains("`networks.localhost.accounts[3].password_env`"));
+        assert!(!err.contains("accounts[4]"));
+        assert!(!err.contains("accounts[5]"));
+    }
+
+    #[test]
+    fn test_parse() {
+        let parse = |fields: &str| {
+            let snapper =
+                alloc::format!("[project]\n[solidity]\nversion = \"0.8.17\"\n[networks]\n{fields}");
+            toml::from_str::<SnapperFile>(&snapper)
+                .unwrap_err()
+                .to_string()
+        };
+
+        let err = parse(
+            "[library.\"Locker.sol\"]\n\
+             LockerLib = \"0x5fbdb2315678afecb367f032d93F642f64180aa3\"",
+        );
+        assert!(err.contains(
+            "invalid EIP-55 checksum of `0x5fbdb2315678afecb367f032d93F642f64180aa3`, \
+             expected `0x5FbDB2315678afecb367f032d93F642f64180aa3`"
+        ));
+
+        let err = parse("[library.\"lib/Math.sol\"]\nMath = \"0x1234567890\"");
+        assert!(err.contains("invalid address `0x1234567890`, expected `0x` and 40 hex digits"));
+
+        let network = "[networks.localhost]\nurl = \"http://127.0.0.1:8545/\"\n";
+
+        let err = parse(&alloc::format!("{network}accounts = [\"\"]"));
+        assert!(err.contains("invalid private key, expected 32 bytes hex"));
+
+        let err = parse(&alloc::format!(
+            "{network}accounts = [{{ remote = \"http://127.0.0.1:8550/\", address = \"0x1234\" }}]"
+        ));
+        assert!(err.contains("invalid address `0x1234`"));
+    }
+}
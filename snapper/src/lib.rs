@@ -1,2 +1,42 @@
 #[cfg(feature = "build")]
-pub use snapper_build::{build, Builder};
+pub use snapper_build::{build, constants, Builder, BuiltinGenerator, Contract, Generator};
+
+/// Link libraries into `<Contract>.bytecode.unlinked.json` artifacts at
+/// deploy time.
+pub use snapper_core::link;
+
+/// ABI encoding of bindings generated by `BuiltinGenerator`.
+pub use snapper_core::abi;
+
+pub use snapper_core::{GasPolicy, Network, SnapperFile, Wei};
+
+#[cfg(feature = "signer")]
+mod network;
+#[cfg(feature = "signer")]
+pub use network::*;
+
+#[cfg(feature = "signer")]
+mod signer;
+#[cfg(feature = "signer")]
+pub use signer::*;
+
+/// Types of transactions and signatures used by `Signer`.
+#[cfg(feature = "signer")]
+pub use ethers_core;
+
+/// Include bindings of all contracts generated by `snapper::build()`, as
+/// modules mirroring the contracts directory.
+///
+/// ```ignore
+/// mod contracts {
+///     snapper::contracts!();
+/// }
+///
+/// use contracts::token::erc20::ERC20;
+/// ```
+#[macro_export]
+macro_rules! contracts {
+    () => {
+        include!(concat!(env!("OUT_DIR"), "/snapper_contracts.rs"));
+    };
+}
@@ -0,0 +1,445 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Result};
+use ethers_core::types::{
+    transaction::eip2718::TypedTransaction, Address, Eip1559TransactionRequest, TransactionReceipt,
+    H256, U256,
+};
+use serde_json::{json, Value};
+use snapper_core::{GasPolicy, Network, SnapperFile};
+
+use crate::Signer;
+
+/// Active network set by `SNAPPER_NETWORK`, select library addresses with
+/// `SnapperFile::libraries`.
+pub fn network() -> Option<String> {
+    env::var("SNAPPER_NETWORK").ok().filter(|v| !v.is_empty())
+}
+
+/// Path of `Snapper.toml`, set by `SNAPPER_MANIFEST` or found in
+/// `CARGO_MANIFEST_DIR`, then current directory.
+pub fn manifest_path() -> PathBuf {
+    if let Ok(path) = env::var("SNAPPER_MANIFEST") {
+        return PathBuf::from(path);
+    }
+
+    match env::var("CARGO_MANIFEST_DIR") {
+        Ok(dir) => PathBuf::from(dir).join("Snapper.toml"),
+        Err(_) => PathBuf::from("Snapper.toml"),
+    }
+}
+
+/// Load and validate `Snapper.toml`, see `manifest_path`.
+pub fn load() -> Result<SnapperFile> {
+    Ok(snapper_core::load_snapper_path(&manifest_path())?)
+}
+
+/// Network connected by `connect`, chain id is checked.
+#[derive(Debug, Clone)]
+pub struct Connection {
+    pub name: String,
+    /// Directory of `Snapper.toml`, keystore paths are relative to it.
+    pub root: PathBuf,
+    pub network: Network,
+    pub chain_id: u64,
+}
+
+impl Connection {
+    pub fn url(&self) -> &str {
+        &self.network.url
+    }
+
+    pub fn gas_policy(&self) -> Result<GasPolicy> {
+        self.network.gas_policy().map_err(|e| anyhow!("{e}"))
+    }
+
+    pub fn gas_limit(&self, estimate: u64) -> u64 {
+        self.network.gas_limit(estimate)
+    }
+
+    pub fn confirmations(&self) -> u64 {
+        self.network.confirmations
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.network.timeout)
+    }
+
+    pub fn explorer_url(&self) -> Option<&str> {
+        self.network.explorer_url.as_deref()
+    }
+
+    pub fn explorer_api_key(&self) -> Option<&str> {
+        self.network.explorer_api_key.as_deref()
+    }
+
+    /// Send JSON-RPC request, returns `result` of response.
+    pub fn request(&self, method: &str, params: Value) -> Result<Value> {
+        request(&self.network.url, self.timeout(), method, params)
+    }
+
+    /// Send JSON-RPC request returning a quantity, like `eth_gasPrice`.
+    fn quantity(&self, method: &str, params: Value) -> Result<U256> {
+        Ok(serde_json::from_value(self.request(method, params)?)?)
+    }
+
+    /// Fill `tx` sent by `from` with chain id, nonce, fees by gas policy and
+    /// gas limit by `gas_multiplier`. Transaction type follows gas policy,
+    /// fees unset in `Snapper.toml` are suggested by node.
+    pub fn fill_transaction(&self, tx: &mut TypedTransaction, from: Address) -> Result<()> {
+        tx.set_from(from);
+        tx.set_chain_id(self.chain_id);
+
+        if tx.nonce().is_none() {
+            let nonce = self.quantity("eth_getTransactionCount", json!([from, "pending"]))?;
+            tx.set_nonce(nonce);
+        }
+
+        match self.gas_policy()? {
+            GasPolicy::Legacy { gas_price } => {
+                if let TypedTransaction::Eip1559(inner) = tx {
+                    *tx = TypedTransaction::Legacy(inner.clone().into());
+                }
+
+                tx.set_gas_price(gas_price.0);
+            }
+            GasPolicy::Eip1559 {
+                max_fee,
+                priority_fee,
+            } => {
+                let priority_fee = match priority_fee {
+                    Some(fee) => U256::from(fee.0),
+                    None => self.quantity("eth_maxPriorityFeePerGas", json!([]))?,
+                };
+
+                let max_fee = match max_fee {
+                    Some(fee) => U256::from(fee.0),
+                    None => {
+                        let block =
+                            self.request("eth_getBlockByNumber", json!(["latest", false]))?;
+                        let base_fee: U256 = serde_json::from_value(block["baseFeePerGas"].clone())
+                            .map_err(|_| anyhow!("Network `{}` has no base fee", self.name))?;
+
+                        base_fee * 2 + priority_fee
+                    }
+                };
+
+                *tx = eip1559(tx)
+                    .max_fee_per_gas(max_fee)
+                    .max_priority_fee_per_gas(priority_fee)
+                    .into();
+            }
+        }
+
+        if tx.gas().is_none() {
+            let estimate = self.quantity("eth_estimateGas", json!([tx]))?;
+            let estimate = u64::try_from(estimate).map_err(|e| anyhow!("Invalid gas: {e}"))?;
+            tx.set_gas(self.gas_limit(estimate));
+        }
+
+        Ok(())
+    }
+
+    /// Fill `tx` by `fill_transaction`, sign it by `signer` and send it,
+    /// returns receipt after `confirmations`.
+    pub fn send_transaction(
+        &self,
+        signer: &Signer,
+        mut tx: TypedTransaction,
+    ) -> Result<TransactionReceipt> {
+        self.fill_transaction(&mut tx, signer.address())?;
+
+        let raw = signer.sign_transaction(&tx)?;
+        let hash = self.request("eth_sendRawTransaction", json!([raw]))?;
+
+        self.wait_for_receipt(serde_json::from_value(hash)?)
+    }
+
+    /// Wait until transaction `hash` has `confirmations` blocks, error after
+    /// `timeout`.
+    pub fn wait_for_receipt(&self, hash: H256) -> Result<TransactionReceipt> {
+        let deadline = Instant::now() + self.timeout();
+
+        loop {
+            let receipt = self.request("eth_getTransactionReceipt", json!([hash]))?;
+
+            if !receipt.is_null() {
+                let receipt: TransactionReceipt = serde_json::from_value(receipt)?;
+
+                if let Some(mined) = receipt.block_number {
+                    let latest = self.quantity("eth_blockNumber", json!([]))?;
+
+                    if latest + 1 >= U256::from(mined.as_u64()) + self.confirmations() {
+                        return Ok(receipt);
+                    }
+                }
+            }
+
+            if Instant::now() >= deadline {
+                bail!(
+                    "Transaction {hash:?} is not confirmed in {}s",
+                    self.network.timeout
+                );
+            }
+
+            thread::sleep(Duration::from_secs(1));
+        }
+    }
+
+    /// Signers of all `accounts` of network, in order.
+    pub fn signers(&self) -> Result<Vec<Signer>> {
+        let mut res = Vec::new();
+
+        for account in &self.network.accounts {
+            res.extend(Signer::from_account(
+                account,
+                &self.root,
+                self.chain_id,
+                self.timeout(),
+            )?);
+        }
+
+        Ok(res)
+    }
+}
+
+/// EIP-1559 transaction with fields of `tx`, fees are not copied.
+fn eip1559(tx: &TypedTransaction) -> Eip1559TransactionRequest {
+    if let TypedTransaction::Eip1559(inner) = tx {
+        return inner.clone();
+    }
+
+    let mut res = Eip1559TransactionRequest::new();
+    res.from = tx.from().copied();
+    res.to = tx.to().cloned();
+    res.gas = tx.gas().copied();
+    res.value = tx.value().copied();
+    res.data = tx.data().cloned();
+    res.nonce = tx.nonce().copied();
+    res.chain_id = tx.chain_id();
+
+    if let Some(access_list) = tx.access_list() {
+        res.access_list = access_list.clone();
+    }
+
+    res
+}
+
+/// Send JSON-RPC request to `url`, returns `result` of response.
+pub(crate) fn request(url: &str, timeout: Duration, method: &str, params: Value) -> Result<Value> {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+
+    let mut response: Value = attohttpc::post(url)
+        .timeout(timeout)
+        .json(&body)?
+        .send()?
+        .error_for_status()?
+        .json()?;
+
+    if let Some(error) = response.get("error") {
+        bail!("Request `{method}` failed: {error}");
+    }
+
+    Ok(response["result"].take())
+}
+
+/// Connect network `name` of `Snapper.toml`, default is `SNAPPER_NETWORK`.
+///
+/// Chain id returned by `eth_chainId` must equal to `chain_id` if set.
+pub fn connect(name: Option<&str>) -> Result<Connection> {
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => network().ok_or_else(|| anyhow!("No network selected, set `SNAPPER_NETWORK`"))?,
+    };
+
+    let snapper_file = load()?;
+
+    let network = snapper_file
+        .networks
+        .get(&name)
+        .cloned()
+        .ok_or_else(|| anyhow!("Network `{name}` not found in Snapper.toml"))?;
+
+    let timeout = Duration::from_secs(network.timeout);
+    let result = request(&network.url, timeout, "eth_chainId", json!([]))?;
+    let result = result
+        .as_str()
+        .ok_or_else(|| anyhow!("Invalid `eth_chainId` response: {result}"))?;
+    let chain_id = u64::from_str_radix(result.trim_start_matches("0x"), 16)?;
+
+    if let Some(expected) = network.chain_id {
+        if expected != chain_id {
+            bail!("Network `{name}` expects chain id {expected}, but node reports {chain_id}");
+        }
+    }
+
+    let manifest = manifest_path();
+    let root = manifest
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .to_path_buf();
+
+    Ok(Connection {
+        name,
+        root,
+        network,
+        chain_id,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        path::PathBuf,
+        thread,
+    };
+
+    use ethers_core::types::{
+        transaction::eip2718::TypedTransaction, TransactionReceipt, TransactionRequest, H256, U256,
+    };
+    use ethers_signers::LocalWallet;
+    use serde_json::{json, Value};
+
+    use super::Connection;
+    use crate::Signer;
+
+    /// Stand-in JSON-RPC node, `handler` returns result of method and params.
+    pub(crate) fn serve<F>(handler: F) -> String
+    where
+        F: Fn(&str, &Value) -> Value + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    let line = line.trim().to_ascii_lowercase();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(v) = line.strip_prefix("content-length:") {
+                        length = v.trim().parse().unwrap();
+                    }
+                }
+
+                let mut body = vec![0u8; length];
+                reader.read_exact(&mut body).unwrap();
+                let request: Value = serde_json::from_slice(&body).unwrap();
+
+                let result = handler(request["method"].as_str().unwrap(), &request["params"]);
+
+                let response = json!({"jsonrpc": "2.0", "id": request["id"], "result": result});
+                let response = response.to_string();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                    response.len()
+                )
+                .unwrap();
+            }
+        });
+
+        url
+    }
+
+    fn connection(url: String, network: Value) -> Connection {
+        let mut network = network;
+        network["url"] = json!(url);
+        network["accounts"] = json!([]);
+
+        Connection {
+            name: "localhost".to_string(),
+            root: PathBuf::new(),
+            network: serde_json::from_value(network).unwrap(),
+            chain_id: 31337,
+        }
+    }
+
+    fn node(method: &str, _params: &Value) -> Value {
+        match method {
+            "eth_getTransactionCount" => json!("0x7"),
+            "eth_estimateGas" => json!("0x5208"),
+            "eth_maxPriorityFeePerGas" => json!("0x3b9aca00"),
+            "eth_getBlockByNumber" => json!({ "baseFeePerGas": "0x64" }),
+            "eth_sendRawTransaction" => json!(H256::repeat_byte(1)),
+            "eth_getTransactionReceipt" => {
+                let receipt = TransactionReceipt {
+                    transaction_hash: H256::repeat_byte(1),
+                    block_number: Some(5.into()),
+                    ..Default::default()
+                };
+                json!(receipt)
+            }
+            "eth_blockNumber" => json!("0x6"),
+            _ => Value::Null,
+        }
+    }
+
+    #[test]
+    fn test_transaction() {
+        let url = serve(node);
+        let tx: TypedTransaction = TransactionRequest::new().value(1).into();
+
+        let legacy = connection(
+            url.clone(),
+            json!({ "gas_price": "2 gwei", "gas_multiplier": 1.5 }),
+        );
+        let mut filled = tx.clone();
+        legacy
+            .fill_transaction(&mut filled, Default::default())
+            .unwrap();
+
+        assert!(matches!(filled, TypedTransaction::Legacy(_)));
+        assert_eq!(filled.chain_id(), Some(31337.into()));
+        assert_eq!(filled.nonce(), Some(&U256::from(7)));
+        assert_eq!(filled.gas_price(), Some(U256::from(2_000_000_000u64)));
+        assert_eq!(filled.gas(), Some(&U256::from(31_500)));
+
+        let eip1559 = connection(url.clone(), json!({ "confirmations": 2 }));
+        let mut filled = tx.clone();
+        eip1559
+            .fill_transaction(&mut filled, Default::default())
+            .unwrap();
+
+        let TypedTransaction::Eip1559(inner) = &filled else {
+            panic!("expect EIP-1559 transaction");
+        };
+        assert_eq!(
+            inner.max_priority_fee_per_gas,
+            Some(1_000_000_000u64.into())
+        );
+        assert_eq!(inner.max_fee_per_gas, Some(1_000_000_200u64.into()));
+        assert_eq!(inner.gas, Some(21_000.into()));
+
+        let wallet: LocalWallet =
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+                .parse()
+                .unwrap();
+        let receipt = eip1559
+            .send_transaction(&Signer::Local(wallet), tx)
+            .unwrap();
+        assert_eq!(receipt.transaction_hash, H256::repeat_byte(1));
+    }
+}
@@ -0,0 +1,302 @@
+use std::{env, path::Path, time::Duration};
+
+use anyhow::{anyhow, bail, Result};
+use ethers_core::{
+    types::{transaction::eip2718::TypedTransaction, Address, Bytes, Signature},
+    utils::hash_message,
+};
+use ethers_signers::{coins_bip39::English, LocalWallet, MnemonicBuilder, Signer as _};
+use serde_json::{json, Value};
+use snapper_core::Account;
+
+use crate::network::request;
+
+/// Account of network able to sign messages and transactions.
+#[derive(Debug, Clone)]
+pub enum Signer {
+    /// Key held in process, from private key, mnemonic or keystore.
+    Local(LocalWallet),
+    Remote(RemoteSigner),
+}
+
+/// Account managed by JSON-RPC signer, like clef or a node.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    pub url: String,
+    pub address: Address,
+    pub timeout: Duration,
+}
+
+impl Signer {
+    /// Signers of `account`, mnemonic and remote signer may give more than
+    /// one. Keystore path is relative to `root`.
+    pub fn from_account(
+        account: &Account,
+        root: &Path,
+        chain_id: u64,
+        timeout: Duration,
+    ) -> Result<Vec<Self>> {
+        let res = match account {
+            Account::PrivateKey(key) => {
+                let wallet = LocalWallet::from_bytes(key.as_bytes())?;
+                vec![Self::Local(wallet.with_chain_id(chain_id))]
+            }
+            Account::Mnemonic {
+                mnemonic,
+                path,
+                index,
+                count,
+            } => {
+                let end = index.checked_add(*count).ok_or_else(|| {
+                    anyhow!("Mnemonic `index` {index} plus `count` {count} overflows")
+                })?;
+
+                let mut res = Vec::new();
+
+                for i in *index..end {
+                    let wallet = MnemonicBuilder::<English>::default()
+                        .phrase(mnemonic.as_str())
+                        .derivation_path(&format!("{}/{i}", path.trim_end_matches('/')))?
+                        .build()?;
+
+                    res.push(Self::Local(wallet.with_chain_id(chain_id)));
+                }
+
+                res
+            }
+            Account::Keystore {
+                keystore,
+                password_env,
+            } => {
+                let password = env::var(password_env)
+                    .map_err(|_| anyhow!("Keystore password `{password_env}` is not set"))?;
+                let wallet = LocalWallet::decrypt_keystore(root.join(keystore), password)?;
+
+                vec![Self::Local(wallet.with_chain_id(chain_id))]
+            }
+            Account::Remote { remote, address } => {
+                let addresses = match address {
+                    Some(address) => vec![Address::from(address.0)],
+                    None => {
+                        let result = request(remote, timeout, "eth_accounts", json!([]))?;
+                        serde_json::from_value(result)?
+                    }
+                };
+
+                addresses
+                    .into_iter()
+                    .map(|address| {
+                        Self::Remote(RemoteSigner {
+                            url: remote.clone(),
+                            address,
+                            timeout,
+                        })
+                    })
+                    .collect()
+            }
+        };
+
+        Ok(res)
+    }
+
+    pub fn address(&self) -> Address {
+        match self {
+            Self::Local(wallet) => wallet.address(),
+            Self::Remote(remote) => remote.address,
+        }
+    }
+
+    /// Sign `message` with EIP-191 prefix, like `eth_sign`.
+    pub fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        match self {
+            Self::Local(wallet) => Ok(wallet.sign_hash(hash_message(message))?),
+            Self::Remote(remote) => {
+                let params = json!([remote.address, Bytes::from(message.to_vec())]);
+                let result = remote.request("eth_sign", params)?;
+                let bytes: Bytes = serde_json::from_value(result)?;
+
+                Ok(Signature::try_from(bytes.as_ref())?)
+            }
+        }
+    }
+
+    /// Sign `tx`, returns raw transaction for `eth_sendRawTransaction`.
+    pub fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Bytes> {
+        let mut tx = tx.clone();
+        tx.set_from(self.address());
+
+        match self {
+            Self::Local(wallet) => {
+                if tx.chain_id().is_none() {
+                    tx.set_chain_id(wallet.chain_id());
+                }
+
+                let signature = wallet.sign_transaction_sync(&tx)?;
+                Ok(tx.rlp_signed(&signature))
+            }
+            Self::Remote(remote) => {
+                let result = remote.request("eth_signTransaction", json!([tx]))?;
+
+                // Geth returns `{ raw, tx }`, others return raw transaction.
+                let raw = match result.get("raw") {
+                    Some(raw) => raw.clone(),
+                    None => result,
+                };
+
+                Ok(serde_json::from_value(raw)?)
+            }
+        }
+    }
+}
+
+impl RemoteSigner {
+    fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let result = request(&self.url, self.timeout, method, params)?;
+
+        if result.is_null() {
+            bail!("Remote signer returns no result for `{method}`");
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{path::Path, time::Duration};
+
+    use ethers_core::{
+        types::{transaction::eip2718::TypedTransaction, Address, TransactionRequest},
+        utils::rlp::Rlp,
+    };
+    use ethers_signers::{LocalWallet, Signer as _};
+    use serde_json::{json, Value};
+    use snapper_core::Account;
+
+    use super::Signer;
+    use crate::network;
+
+    const MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+    fn signers(account: Account) -> Vec<Signer> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        Signer::from_account(&account, &root, 31337, Duration::from_secs(5)).unwrap()
+    }
+
+    fn address(s: &str) -> Address {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_local() {
+        let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let signer = &signers(Account::PrivateKey(key.parse().unwrap()))[0];
+        assert_eq!(
+            signer.address(),
+            address("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
+        );
+
+        let signature = signer.sign_message(b"snapper").unwrap();
+        signature
+            .verify("snapper", signer.address())
+            .expect("valid signature");
+
+        let mnemonic = signers(Account::Mnemonic {
+            mnemonic: MNEMONIC.to_string(),
+            path: "m/44'/60'/0'/0".to_string(),
+            index: 0,
+            count: 2,
+        });
+        assert_eq!(mnemonic.len(), 2);
+        assert_eq!(mnemonic[0].address(), signer.address());
+        assert_eq!(
+            mnemonic[1].address(),
+            address("0x70997970C51812dc3A010C7d01b50e0d17dc79C8")
+        );
+
+        let overflow = Account::Mnemonic {
+            mnemonic: MNEMONIC.to_string(),
+            path: "m/44'/60'/0'/0".to_string(),
+            index: u32::MAX,
+            count: 2,
+        };
+        assert!(Signer::from_account(&overflow, Path::new(""), 1, Duration::from_secs(5)).is_err());
+
+        std::env::set_var("SNAPPER_TEST_KEYSTORE_PASSWORD", "snapper");
+        let keystore = signers(Account::Keystore {
+            keystore: "keystore.json".to_string(),
+            password_env: "SNAPPER_TEST_KEYSTORE_PASSWORD".to_string(),
+        });
+        assert_eq!(keystore[0].address(), mnemonic[1].address());
+
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(signer.address())
+            .value(1)
+            .nonce(0)
+            .gas(21000)
+            .gas_price(1)
+            .into();
+        let raw = signer.sign_transaction(&tx).unwrap();
+        assert!(!raw.is_empty());
+    }
+
+    /// Stand-in JSON-RPC signer.
+    fn serve(wallet: LocalWallet) -> String {
+        network::test::serve(move |method, params| match method {
+            "eth_accounts" => json!([wallet.address()]),
+            "eth_sign" => {
+                let message: ethers_core::types::Bytes =
+                    serde_json::from_value(params[1].clone()).unwrap();
+                let hash = ethers_core::utils::hash_message(message.as_ref());
+                let signature = wallet.sign_hash(hash).unwrap();
+                json!(format!("0x{signature}"))
+            }
+            // Geth style `{ raw, tx }`.
+            "eth_signTransaction" => {
+                let tx: TypedTransaction = serde_json::from_value(params[0].clone()).unwrap();
+                let signature = wallet.sign_transaction_sync(&tx).unwrap();
+                json!({ "raw": tx.rlp_signed(&signature), "tx": params[0] })
+            }
+            _ => Value::Null,
+        })
+    }
+
+    #[test]
+    fn test_remote() {
+        let wallet: LocalWallet =
+            "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+                .parse()
+                .unwrap();
+        let url = serve(wallet.clone());
+
+        let remote = signers(Account::Remote {
+            remote: url,
+            address: None,
+        });
+        assert_eq!(remote.len(), 1);
+        assert_eq!(remote[0].address(), wallet.address());
+
+        let signature = remote[0].sign_message(b"snapper").unwrap();
+        signature
+            .verify("snapper", wallet.address())
+            .expect("valid signature");
+
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(wallet.address())
+            .value(1)
+            .nonce(0)
+            .gas(21000)
+            .gas_price(1)
+            .chain_id(31337)
+            .into();
+        let raw = remote[0].sign_transaction(&tx).unwrap();
+
+        let (signed, signature) = TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap();
+        assert_eq!(signed.from(), Some(&wallet.address()));
+        assert_eq!(signed.value(), Some(&1.into()));
+        assert_eq!(
+            signature.recover(signed.sighash()).unwrap(),
+            wallet.address()
+        );
+    }
+}
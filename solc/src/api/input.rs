@@ -3,7 +3,7 @@
 use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
-use snapper_core::EvmVersion;
+pub use snapper_core::{BytecodeHash, DebugInfo, EvmVersion, RevertStrings};
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub enum InputLanguage {
@@ -17,7 +17,10 @@ pub struct SourceFile {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub keccak256: Option<String>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub urls: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -60,28 +63,6 @@ pub struct Optimizer {
     pub details: OptimizerDetails,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub enum RevertStrings {
-    #[serde(rename = "default")]
-    Default,
-    #[serde(rename = "strip")]
-    Strip,
-    #[serde(rename = "debug")]
-    Debug,
-    #[serde(rename = "verboseDebug")]
-    VerboseDebug,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub enum DebugInfo {
-    #[serde(rename = "location")]
-    Location,
-    #[serde(rename = "snippet")]
-    Snippet,
-    #[serde(rename = "*")]
-    All,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SettingsDebug {
     #[serde(rename = "revertStrings")]
@@ -90,24 +71,17 @@ pub struct SettingsDebug {
     pub debug_info: Vec<DebugInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub enum BytecodeHash {
-    #[serde(rename = "none")]
-    None,
-    #[serde(rename = "ipfs")]
-    Ipfs,
-    #[serde(rename = "bzzr1")]
-    Bzzr1,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Metadata {
     #[serde(rename = "appendCBOR")]
-    pub append_cbor: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub append_cbor: Option<bool>,
     #[serde(rename = "useLiteralContent")]
-    pub use_literal_content: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_literal_content: Option<bool>,
     #[serde(rename = "bytecodeHash")]
-    pub bytecode_hash: BytecodeHash,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytecode_hash: Option<BytecodeHash>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -186,7 +160,7 @@ pub struct ModelChecker {
     pub timeout: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OutputSelection {
     #[serde(rename = "ast")]
     Ast,
@@ -244,7 +218,8 @@ pub struct Settings {
     pub remappings: Vec<String>,
     pub optimizer: Optimizer,
     #[serde(rename = "evmVersion")]
-    pub evm_version: EvmVersion,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evm_version: Option<EvmVersion>,
     #[serde(rename = "viaIR")]
     pub via_ir: bool,
     pub debug: SettingsDebug,
@@ -252,7 +227,7 @@ pub struct Settings {
     pub metadata: Option<Metadata>,
     pub libraries: BTreeMap<String, BTreeMap<String, String>>,
     #[serde(rename = "outputSelection")]
-    pub output_selection: HashMap<String, HashMap<String, Vec<OutputSelection>>>,
+    pub output_selection: BTreeMap<String, BTreeMap<String, Vec<OutputSelection>>>,
     #[serde(rename = "modelChecker")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model_checker: Option<ModelChecker>,
@@ -261,7 +236,7 @@ pub struct Settings {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CompilerInput {
     pub language: InputLanguage,
-    pub sources: HashMap<String, SourceFile>,
+    pub sources: BTreeMap<String, SourceFile>,
     pub settings: Settings,
 }
 
@@ -273,6 +248,6 @@ mod test {
     fn test() {
         let config = include_str!("input.json");
 
-        let _input: CompilerInput = serde_json::from_str(&config).unwrap();
+        let _input: CompilerInput = serde_json::from_str(config).unwrap();
     }
 }
@@ -1,22 +1,24 @@
 //! Type collections for solc json api output
 
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Deref};
 
 use primitive_types::U256;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use snapper_core::link::{LinkReference, LinkReferences, UnlinkedBytecode};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SourceLocation {
     pub file: String,
-    pub start: u32,
-    pub end: u32,
+    /// Byte offset, `-1` if unknown.
+    pub start: i32,
+    pub end: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SecondarySourceLocations {
     #[serde(flatten)]
-    location: SourceLocation,
-    message: String,
+    pub location: SourceLocation,
+    pub message: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -114,11 +116,17 @@ pub enum ABIInfo {
     },
 }
 
+/// Field of tuple, nested tuples have their own components.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Component {
     pub name: String,
     #[serde(rename = "type")]
     pub ty: String,
+    /// Solidity type, like `struct Lock.Info[]`.
+    #[serde(rename = "internalType")]
+    pub internal_type: Option<String>,
+    #[serde(default)]
+    pub components: Vec<Component>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -126,6 +134,8 @@ pub struct Value {
     pub name: String,
     #[serde(rename = "type")]
     pub ty: String,
+    #[serde(rename = "internalType")]
+    pub internal_type: Option<String>,
     #[serde(default)]
     pub components: Vec<Component>,
     #[serde(default)]
@@ -200,35 +210,161 @@ pub struct StorageLayout {
     pub types: HashMap<String, StorageType>,
 }
 
+/// Source generated by compiler, like Yul utility functions.
 #[derive(Debug, Serialize, Deserialize)]
-pub struct GeneratedSources {}
+pub struct GeneratedSource {
+    /// Yul AST.
+    pub ast: serde_json::Value,
+    pub contents: String,
+    /// Source id used in source maps.
+    pub id: u32,
+    pub language: String,
+    pub name: String,
+}
 
 #[derive(Debug, Serialize, Deserialize)]
-pub struct LinkReferencePos {
-    pub start: u64,
-    pub length: u64,
+pub struct FunctionDebugData {
+    /// Byte offset of function entry in bytecode.
+    #[serde(rename = "entryPoint")]
+    pub entry_point: Option<u64>,
+    /// AST id of function definition.
+    pub id: Option<u64>,
+    #[serde(rename = "parameterSlots")]
+    pub parameter_slots: Option<u64>,
+    #[serde(rename = "returnSlots")]
+    pub return_slots: Option<u64>,
+}
+
+pub type LinkReferencePos = LinkReference;
+
+/// Bytecode object, unlinked when it references libraries without address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BytecodeObject {
+    Linked(Vec<u8>),
+    /// Hex string with `__$<hash>$__` placeholders.
+    Unlinked(String),
+}
+
+impl BytecodeObject {
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Linked(b) => Some(b),
+            Self::Unlinked(_) => None,
+        }
+    }
+
+    pub fn is_linked(&self) -> bool {
+        matches!(self, Self::Linked(_))
+    }
+
+    /// Size in bytes.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Linked(b) => b.len(),
+            Self::Unlinked(s) => s.len() / 2,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Serialize for BytecodeObject {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Linked(b) => serializer.serialize_str(&hex::encode(b)),
+            Self::Unlinked(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BytecodeObject {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+
+        match hex::decode(s.strip_prefix("0x").unwrap_or(&s)) {
+            Ok(b) => Ok(Self::Linked(b)),
+            Err(_) => Ok(Self::Unlinked(s)),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Bytecode {
-    #[serde(serialize_with = "hex::serialize")]
-    #[serde(deserialize_with = "hex::deserialize")]
-    pub object: Vec<u8>,
+    /// Debug data of internal functions, keyed by `@<name>_<id>`.
+    #[serde(rename = "functionDebugData")]
+    #[serde(default)]
+    pub function_debug_data: HashMap<String, FunctionDebugData>,
+    pub object: BytecodeObject,
+    #[serde(default)]
     pub opcodes: String,
     #[serde(rename = "sourceMap")]
+    #[serde(default)]
     pub source_map: String,
     #[serde(rename = "generatedSources")]
-    pub generated_sources: Vec<GeneratedSources>,
+    #[serde(default)]
+    pub generated_sources: Vec<GeneratedSource>,
+    /// Unlinked libraries, keyed by file then library name.
     #[serde(rename = "linkReferences")]
-    pub link_references: HashMap<String, HashMap<String, Vec<LinkReferencePos>>>,
+    #[serde(default)]
+    pub link_references: LinkReferences,
 }
 
+impl Bytecode {
+    /// Code size in bytes.
+    pub fn size(&self) -> usize {
+        self.object.len()
+    }
+
+    /// Link library `lib` defined in `file` at `address`.
+    ///
+    /// Bytecode becomes `BytecodeObject::Linked` once all libraries linked.
+    pub fn link(&mut self, file: &str, lib: &str, address: &str) -> crate::Result<()> {
+        let object = match &self.object {
+            BytecodeObject::Unlinked(s) => s.clone(),
+            BytecodeObject::Linked(_) => return Err(snapper_core::Error::UnknownLibrary.into()),
+        };
+
+        let mut unlinked = UnlinkedBytecode {
+            object,
+            link_references: std::mem::take(&mut self.link_references),
+        };
+
+        let res = unlinked.link(file, lib, address);
+
+        self.link_references = unlinked.link_references;
+        res?;
+
+        self.object = if self.link_references.is_empty() {
+            BytecodeObject::Linked(snapper_core::hex::decode(&unlinked.object)?)
+        } else {
+            BytecodeObject::Unlinked(unlinked.object)
+        };
+
+        Ok(())
+    }
+}
+
+/// Runtime bytecode, same as `Bytecode` with immutable references.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeployedBytecode {
+    #[serde(flatten)]
+    pub bytecode: Bytecode,
+    /// Positions of immutables, keyed by AST id of declaration.
     #[serde(rename = "immutableReferences")]
+    #[serde(default)]
     pub immutable_references: HashMap<String, Vec<LinkReferencePos>>,
 }
 
+impl Deref for DeployedBytecode {
+    type Target = Bytecode;
+
+    fn deref(&self) -> &Bytecode {
+        &self.bytecode
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct Selector {
@@ -268,27 +404,34 @@ pub struct EWasm {
     pub wasm: String,
 }
 
+/// Evm outputs, fields are `None` unless selected in `outputSelection`.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Evm {
     pub assembly: Option<String>,
-    pub bytecode: Bytecode,
+    pub bytecode: Option<Bytecode>,
     #[serde(rename = "deployedBytecode")]
-    pub deployed_bytecode: DeployedBytecode,
+    pub deployed_bytecode: Option<DeployedBytecode>,
     #[serde(rename = "methodIdentifiers")]
     #[serde(default)]
     pub method_identifiers: HashMap<String, Selector>,
     #[serde(rename = "gasEstimates")]
-    pub gas_estimates: GasEstimates,
+    pub gas_estimates: Option<GasEstimates>,
 }
 
+/// Contract outputs, fields are `None` unless selected in `outputSelection`.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Contract {
+    #[serde(default)]
     pub abi: Vec<ABIInfo>,
     pub metadata: Option<String>,
     pub ir: Option<String>,
+    #[serde(rename = "irOptimized")]
+    pub ir_optimized: Option<String>,
     #[serde(rename = "storageLayout")]
     pub storage_layout: Option<StorageLayout>,
-    pub evm: Evm,
+    pub devdoc: Option<serde_json::Value>,
+    pub userdoc: Option<serde_json::Value>,
+    pub evm: Option<Evm>,
     pub ewasm: Option<EWasm>,
 }
 
@@ -302,13 +445,56 @@ pub struct CompilerOutput {
 
 #[cfg(test)]
 mod test {
-    use crate::CompilerOutput;
+    use crate::{output::Bytecode, CompilerOutput};
 
     #[test]
     fn test() {
         let config = include_str!("output.json");
 
-        let _obj: CompilerOutput = serde_json::from_str(&config).unwrap();
+        let obj: CompilerOutput = serde_json::from_str(config).unwrap();
         // print!("{:#?}", obj);
+
+        let contracts = obj.contracts.unwrap();
+        let evm = contracts["sourceFile.sol"]["ContractName"]
+            .evm
+            .as_ref()
+            .unwrap();
+
+        let bytecode = evm.bytecode.as_ref().unwrap();
+        assert_eq!(
+            bytecode.function_debug_data["@mint_13"].entry_point,
+            Some(128)
+        );
+        assert_eq!(bytecode.generated_sources[0].name, "#utility.yul");
+
+        let deployed = evm.deployed_bytecode.as_ref().unwrap();
+        assert_eq!(deployed.object.as_bytes(), Some(&[0x00, 0xfe][..]));
+        assert_eq!(deployed.immutable_references["3"][0].start, 42);
+    }
+
+    #[test]
+    fn test_link() {
+        let placeholder = format!("__${}$__", "a".repeat(34));
+        let mut bytecode: Bytecode = serde_json::from_value(serde_json::json!({
+            "object": format!("73{placeholder}00"),
+            "linkReferences": {
+                "Lib.sol": { "Lib": [{ "start": 1, "length": 20 }] }
+            }
+        }))
+        .unwrap();
+
+        assert!(!bytecode.object.is_linked());
+        assert_eq!(bytecode.size(), 22);
+        assert!(bytecode.link("Lib.sol", "Other", "0x00").is_err());
+        assert!(bytecode.link("Lib.sol", "Lib", "0x1234").is_err());
+
+        let address = "0x1111111111111111111111111111111111111111";
+        bytecode.link("Lib.sol", "Lib", address).unwrap();
+
+        let mut expected = vec![0x73];
+        expected.extend([0x11; 20]);
+        expected.push(0x00);
+        assert_eq!(bytecode.object.as_bytes(), Some(&expected[..]));
+        assert!(bytecode.link_references.is_empty());
     }
 }
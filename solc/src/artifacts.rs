@@ -0,0 +1,266 @@
+//! Artifact files written from compiler output
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use snapper_core::{link::UnlinkedBytecode, Address, Outputs};
+
+use crate::{input::OutputSelection, Error};
+
+/// How output value is written.
+#[derive(Debug, Clone, Copy)]
+pub enum Format {
+    /// Value as json.
+    Json,
+    /// String value as is.
+    Text,
+    /// Hex string value decoded into bytes, unlinked bytecode is kept as
+    /// json with link references.
+    Hex,
+}
+
+/// Artifact file written for each contract.
+#[derive(Debug, Clone, Copy)]
+pub struct Artifact {
+    /// Json pointer into contract output, like `/evm/bytecode/object`.
+    pub pointer: &'static str,
+    /// File name is `<Contract>.<suffix>`.
+    pub suffix: &'static str,
+    pub format: Format,
+}
+
+const fn artifact(pointer: &'static str, suffix: &'static str, format: Format) -> Artifact {
+    Artifact {
+        pointer,
+        suffix,
+        format,
+    }
+}
+
+/// Output selections and artifacts of `outputs`.
+pub fn selected(outputs: &Outputs) -> (Vec<OutputSelection>, Vec<Artifact>) {
+    let all = [
+        (
+            true,
+            OutputSelection::Abi,
+            vec![artifact("/abi", "abi.json", Format::Json)],
+        ),
+        (
+            outputs.metadata,
+            OutputSelection::Metadata,
+            vec![artifact("/metadata", "metadata.json", Format::Text)],
+        ),
+        (
+            outputs.bytecode,
+            OutputSelection::EvmBytecode,
+            vec![
+                artifact("/evm/bytecode/object", "bytecode", Format::Hex),
+                artifact("/evm/bytecode/opcodes", "opcodes", Format::Text),
+                artifact("/evm/bytecode/sourceMap", "sourcemap", Format::Text),
+            ],
+        ),
+        (
+            outputs.deployed_bytecode,
+            OutputSelection::EvmDeployedBytecode,
+            vec![
+                artifact(
+                    "/evm/deployedBytecode/object",
+                    "deployed.bytecode",
+                    Format::Hex,
+                ),
+                artifact(
+                    "/evm/deployedBytecode/opcodes",
+                    "deployed.opcodes",
+                    Format::Text,
+                ),
+                artifact(
+                    "/evm/deployedBytecode/sourceMap",
+                    "deployed.sourcemap",
+                    Format::Text,
+                ),
+            ],
+        ),
+        (
+            outputs.gas_estimates,
+            OutputSelection::EvmGasEstimates,
+            vec![artifact("/evm/gasEstimates", "gas.json", Format::Json)],
+        ),
+        (
+            outputs.method_identifiers,
+            OutputSelection::EvmMethodIdentifiers,
+            vec![artifact(
+                "/evm/methodIdentifiers",
+                "methods.json",
+                Format::Json,
+            )],
+        ),
+        (
+            outputs.storage_layout,
+            OutputSelection::StorageLayout,
+            vec![artifact("/storageLayout", "storage.json", Format::Json)],
+        ),
+        (
+            outputs.devdoc,
+            OutputSelection::DevDoc,
+            vec![artifact("/devdoc", "devdoc.json", Format::Json)],
+        ),
+        (
+            outputs.userdoc,
+            OutputSelection::UserDoc,
+            vec![artifact("/userdoc", "userdoc.json", Format::Json)],
+        ),
+        (
+            outputs.ir,
+            OutputSelection::IR,
+            vec![artifact("/ir", "ir", Format::Text)],
+        ),
+        (
+            outputs.ir_optimized,
+            OutputSelection::IROptimzed,
+            vec![artifact("/irOptimized", "optimized.ir", Format::Text)],
+        ),
+        (
+            outputs.assembly,
+            OutputSelection::EvmAssembly,
+            vec![artifact("/evm/assembly", "asm", Format::Text)],
+        ),
+    ];
+
+    let mut selection = Vec::new();
+    let mut artifacts = Vec::new();
+
+    for (enabled, s, a) in all {
+        if enabled {
+            selection.push(s);
+            artifacts.extend(a);
+        }
+    }
+
+    (selection, artifacts)
+}
+
+/// Library addresses keyed by file then library name.
+pub type Libraries = BTreeMap<String, BTreeMap<String, Address>>;
+
+/// Directory of artifacts linked with libraries of `network`.
+pub fn network_dir(out_dir: &Path, network: &str) -> PathBuf {
+    out_dir.join(".networks").join(network)
+}
+
+/// Write `artifacts` of `contract` output into `dir`, bytecode is linked
+/// with `libraries`.
+///
+/// Outputs missing from `contract` are skipped.
+pub fn write(
+    dir: &Path,
+    name: &str,
+    contract: &Value,
+    artifacts: &[Artifact],
+    libraries: &Libraries,
+) -> Result<()> {
+    for a in artifacts {
+        let value = match contract.pointer(a.pointer) {
+            Some(Value::Null) | None => continue,
+            Some(v) => v,
+        };
+
+        let path = dir.join(format!("{name}.{}", a.suffix));
+
+        match a.format {
+            Format::Json => fs::write(path, serde_json::to_string(value)?)?,
+            Format::Text => fs::write(path, value.as_str().unwrap_or_default().trim())?,
+            Format::Hex => {
+                let s = value
+                    .as_str()
+                    .ok_or(anyhow!("Invalid {} of {name}", a.pointer))?;
+                write_bytecode(contract, a.pointer, s, &path, libraries)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Write linked bytecode as bytes at `path`, or unlinked bytecode with its
+/// link references as json at `<path>.unlinked.json` for linking at deploy
+/// time. Stale file of the other kind is removed.
+fn write_bytecode(
+    contract: &Value,
+    pointer: &str,
+    object: &str,
+    path: &Path,
+    libraries: &Libraries,
+) -> Result<()> {
+    let unlinked_path = path.with_file_name(format!(
+        "{}.unlinked.json",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    ));
+
+    let bytes = match hex::decode(object.trim_start_matches("0x")) {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            let parent = &pointer[..pointer.rfind('/').unwrap_or_default()];
+            let link_references = contract
+                .pointer(&format!("{parent}/linkReferences"))
+                .cloned()
+                .unwrap_or_else(|| Value::Object(Default::default()));
+
+            let mut unlinked = UnlinkedBytecode {
+                object: object.to_string(),
+                link_references: serde_json::from_value(link_references)?,
+            };
+
+            unlinked.link_all(libraries).map_err(Error::from)?;
+
+            if !unlinked.is_linked() {
+                fs::write(unlinked_path, serde_json::to_string(&unlinked)?)?;
+                let _ = fs::remove_file(path);
+                return Ok(());
+            }
+
+            unlinked.to_bytes().map_err(Error::from)?
+        }
+    };
+
+    fs::write(path, bytes)?;
+    let _ = fs::remove_file(unlinked_path);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use snapper_core::Outputs;
+
+    use super::selected;
+    use crate::input::OutputSelection;
+
+    #[test]
+    fn test_selected() {
+        let outputs = Outputs {
+            bytecode: false,
+            storage_layout: true,
+            ..Default::default()
+        };
+
+        let (selection, artifacts) = selected(&outputs);
+
+        assert!(matches!(selection[0], OutputSelection::Abi));
+        assert!(selection
+            .iter()
+            .any(|s| matches!(s, OutputSelection::StorageLayout)));
+        assert!(!selection
+            .iter()
+            .any(|s| matches!(s, OutputSelection::EvmBytecode)));
+
+        let suffixes: Vec<_> = artifacts.iter().map(|a| a.suffix).collect();
+        assert!(suffixes.contains(&"deployed.sourcemap"));
+        assert!(suffixes.contains(&"storage.json"));
+        assert!(!suffixes.contains(&"bytecode"));
+    }
+}
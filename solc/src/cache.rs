@@ -0,0 +1,248 @@
+//! Incremental compilation cache
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{sources::artifact_dir, Sources};
+
+/// File name of cache of compilation job `name` under artifacts directory.
+pub fn cache_file(name: &str) -> String {
+    format!("snapper-cache-{name}.json")
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Sha256 of source content.
+    pub content_hash: String,
+    /// Resolved source unit names of imports.
+    pub imports: Vec<String>,
+    /// Contracts defined in this source unit.
+    pub contracts: Vec<String>,
+    /// Output was selected, false for units only imported by roots.
+    #[serde(default)]
+    pub output: bool,
+}
+
+/// State of last compilation.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CompilerCache {
+    pub solc_version: String,
+    /// Sha256 of serialized `Settings`.
+    pub settings_hash: String,
+    pub sources: BTreeMap<String, CacheEntry>,
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+impl CompilerCache {
+    /// Load cache of job `name` from `out_dir`, return an empty cache if
+    /// missing or broken.
+    pub fn load(out_dir: &Path, name: &str) -> Self {
+        fs::read(out_dir.join(cache_file(name)))
+            .ok()
+            .and_then(|s| serde_json::from_slice(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, out_dir: &Path, name: &str) -> Result<()> {
+        fs::create_dir_all(out_dir)?;
+        fs::write(
+            out_dir.join(cache_file(name)),
+            serde_json::to_vec_pretty(self)?,
+        )?;
+        Ok(())
+    }
+
+    /// Source units need to be compiled again.
+    ///
+    /// A unit is dirty when it or any unit it imports transitively changed,
+    /// or when it was not output or any of its artifacts is missing.
+    /// Imports which are not roots are compared by content only. Everything
+    /// is dirty when compiler version or settings changed.
+    pub fn dirty(
+        &self,
+        sources: &Sources,
+        solc_version: &str,
+        settings_hash: &str,
+        out_dir: &Path,
+    ) -> BTreeSet<String> {
+        if self.solc_version != solc_version || self.settings_hash != settings_hash {
+            return sources.roots().cloned().collect();
+        }
+
+        let changed: BTreeSet<&String> = sources
+            .iter()
+            .filter(|(name, unit)| match self.sources.get(*name) {
+                Some(entry) => {
+                    let missing = || {
+                        !entry.output
+                            || entry.contracts.iter().any(|c| {
+                                !artifact_dir(out_dir, name)
+                                    .join(format!("{c}.abi.json"))
+                                    .exists()
+                            })
+                    };
+
+                    entry.content_hash != sha256_hex(unit.content.as_bytes())
+                        || entry.imports != unit.imports
+                        || (sources.is_root(name) && missing())
+                }
+                None => true,
+            })
+            .map(|(name, _)| name)
+            .collect();
+
+        sources
+            .roots()
+            .filter(|name| sources.closure([*name]).iter().any(|n| changed.contains(n)))
+            .cloned()
+            .collect()
+    }
+
+    /// Record compiled units and content of imports which are not roots,
+    /// drop units which no longer exist.
+    ///
+    /// Roots importing a changed unit are compiled again, so imports are
+    /// recorded as they are in `sources`.
+    pub fn update(
+        &mut self,
+        sources: &Sources,
+        solc_version: &str,
+        settings_hash: &str,
+        compiled: &BTreeMap<String, Vec<String>>,
+    ) {
+        if self.solc_version != solc_version || self.settings_hash != settings_hash {
+            self.sources.clear();
+            self.solc_version = solc_version.to_string();
+            self.settings_hash = settings_hash.to_string();
+        }
+
+        self.sources.retain(|name, _| sources.get(name).is_some());
+
+        for (name, unit) in sources.iter() {
+            let contracts = match compiled.get(name) {
+                Some(contracts) => contracts,
+                None if sources.is_root(name) => continue,
+                None => &Vec::new(),
+            };
+
+            let entry = CacheEntry {
+                content_hash: sha256_hex(unit.content.as_bytes()),
+                imports: unit.imports.clone(),
+                contracts: contracts.clone(),
+                output: sources.is_root(name),
+            };
+
+            self.sources.insert(name.clone(), entry);
+        }
+    }
+
+    /// Contracts of every root unit in `sources` recorded in cache.
+    pub fn contracts(&self, sources: &Sources) -> BTreeMap<String, Vec<String>> {
+        self.sources
+            .iter()
+            .filter(|(name, _)| sources.is_root(name))
+            .map(|(name, entry)| (name.clone(), entry.contracts.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, fs};
+
+    use crate::Sources;
+
+    use super::CompilerCache;
+
+    #[test]
+    fn test_dirty() {
+        let dir = std::env::temp_dir().join("snapper-cache-test");
+        let _ = fs::remove_dir_all(&dir);
+        let out_dir = dir.join("artifacts");
+        fs::create_dir_all(&out_dir).unwrap();
+
+        fs::write(dir.join("A.sol"), "import \"./B.sol\"; contract A {}").unwrap();
+        fs::write(dir.join("B.sol"), "contract B {}").unwrap();
+        fs::write(dir.join("C.sol"), "contract C {}").unwrap();
+
+        let sources = Sources::collect(&dir).unwrap();
+
+        let mut cache = CompilerCache::default();
+        assert_eq!(cache.dirty(&sources, "0.8.17", "s", &out_dir).len(), 3);
+
+        let mut compiled = BTreeMap::new();
+        for name in ["A", "B", "C"] {
+            let source = format!("{name}.sol");
+            fs::create_dir_all(out_dir.join(&source)).unwrap();
+            fs::write(out_dir.join(&source).join(format!("{name}.abi.json")), "[]").unwrap();
+            compiled.insert(source, vec![name.to_string()]);
+        }
+
+        cache.update(&sources, "0.8.17", "s", &compiled);
+        assert!(cache.dirty(&sources, "0.8.17", "s", &out_dir).is_empty());
+        assert_eq!(cache.dirty(&sources, "0.8.18", "s", &out_dir).len(), 3);
+        assert_eq!(cache.dirty(&sources, "0.8.17", "t", &out_dir).len(), 3);
+
+        fs::write(dir.join("B.sol"), "contract B { uint x; }").unwrap();
+        let sources = Sources::collect(&dir).unwrap();
+
+        let dirty = cache.dirty(&sources, "0.8.17", "s", &out_dir);
+        assert_eq!(
+            dirty.into_iter().collect::<Vec<_>>(),
+            vec!["A.sol", "B.sol"]
+        );
+
+        fs::remove_file(out_dir.join("C.sol").join("C.abi.json")).unwrap();
+        assert!(cache
+            .dirty(&sources, "0.8.17", "s", &out_dir)
+            .contains("C.sol"));
+    }
+
+    #[test]
+    fn test_dirty_subset() {
+        let dir = std::env::temp_dir().join("snapper-cache-subset-test");
+        let _ = fs::remove_dir_all(&dir);
+        let out_dir = dir.join("artifacts");
+        fs::create_dir_all(out_dir.join("A.sol")).unwrap();
+
+        fs::write(dir.join("A.sol"), "import \"./B.sol\"; contract A {}").unwrap();
+        fs::write(dir.join("B.sol"), "contract B {}").unwrap();
+        fs::write(out_dir.join("A.sol").join("A.abi.json"), "[]").unwrap();
+
+        let all = Sources::collect(&dir).unwrap();
+        let sources = all.subset([&"A.sol".to_string()]);
+
+        let mut cache = CompilerCache::default();
+        let dirty = cache.dirty(&sources, "0.8.17", "s", &out_dir);
+        assert_eq!(dirty.into_iter().collect::<Vec<_>>(), vec!["A.sol"]);
+
+        // Output of imports is not selected.
+        let compiled = BTreeMap::from([("A.sol".to_string(), vec!["A".to_string()])]);
+        cache.update(&sources, "0.8.17", "s", &compiled);
+        assert!(cache.dirty(&sources, "0.8.17", "s", &out_dir).is_empty());
+        assert_eq!(cache.contracts(&sources), compiled);
+
+        fs::write(dir.join("B.sol"), "contract B { uint x; }").unwrap();
+        let sources = Sources::collect(&dir).unwrap();
+        let sources = sources.subset([&"A.sol".to_string()]);
+
+        let dirty = cache.dirty(&sources, "0.8.17", "s", &out_dir);
+        assert_eq!(dirty.into_iter().collect::<Vec<_>>(), vec!["A.sol"]);
+
+        // Becoming a root needs output.
+        cache.update(&sources, "0.8.17", "s", &compiled);
+        assert!(cache
+            .dirty(&Sources::collect(&dir).unwrap(), "0.8.17", "s", &out_dir)
+            .contains("B.sol"));
+    }
+}
@@ -0,0 +1,159 @@
+//! Render compiler errors like rustc
+
+use std::fmt::Write;
+
+use crate::{
+    output::{OutputError, Severity, SourceLocation},
+    Sources,
+};
+
+impl Severity {
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::Error)
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Info => "info",
+        }
+    }
+}
+
+/// Line and column of byte offset `pos`, both start from 1.
+pub fn line_col(content: &str, pos: usize) -> (usize, usize) {
+    let pos = pos.min(content.len());
+    let before = &content.as_bytes()[..pos];
+
+    let line = before.iter().filter(|b| **b == b'\n').count() + 1;
+    let line_start = before
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map(|p| p + 1)
+        .unwrap_or(0);
+
+    let col = String::from_utf8_lossy(&before[line_start..])
+        .chars()
+        .count()
+        + 1;
+
+    (line, col)
+}
+
+/// Render a compiler error with file, line, column and source snippet.
+pub fn render(error: &OutputError, sources: &Sources) -> String {
+    render_with(error, |file| sources.get(file).map(|u| u.content.as_str()))
+}
+
+fn render_with<'a, F>(error: &OutputError, lookup: F) -> String
+where
+    F: Fn(&str) -> Option<&'a str>,
+{
+    let mut res = String::new();
+
+    let _ = write!(res, "{}", error.severity.to_str());
+    if let Some(code) = &error.error_code {
+        let _ = write!(res, "[{code}]");
+    }
+    let _ = writeln!(res, ": {}", error.message);
+
+    if let Some(location) = &error.source_location {
+        render_location(&mut res, location, &lookup);
+    }
+
+    for secondary in &error.secondary_source_locations {
+        let _ = writeln!(res, "note: {}", secondary.message);
+        render_location(&mut res, &secondary.location, &lookup);
+    }
+
+    res
+}
+
+fn render_location<'a, F>(res: &mut String, location: &SourceLocation, lookup: &F)
+where
+    F: Fn(&str) -> Option<&'a str>,
+{
+    let content = match lookup(&location.file) {
+        Some(c) if location.start >= 0 => c,
+        _ => {
+            let _ = writeln!(res, " --> {}", location.file);
+            return;
+        }
+    };
+
+    let start = location.start as usize;
+    let end = (location.end.max(location.start) as usize).min(content.len());
+
+    let (line, col) = line_col(content, start);
+    let (end_line, end_col) = line_col(content, end);
+
+    let text = content.lines().nth(line - 1).unwrap_or_default();
+    let width = line.to_string().len();
+
+    let marks = if end_line == line {
+        (end_col - col).max(1)
+    } else {
+        (text.chars().count() + 1 - col).max(1)
+    };
+
+    let pad = " ".repeat(width);
+    let _ = writeln!(res, "{pad}--> {}:{line}:{col}", location.file);
+    let _ = writeln!(res, "{pad} |");
+    let _ = writeln!(res, "{line} | {text}");
+    let _ = writeln!(res, "{pad} | {}{}", " ".repeat(col - 1), "^".repeat(marks));
+}
+
+#[cfg(test)]
+mod test {
+    use crate::output::{OutputError, SecondarySourceLocations, SourceLocation};
+
+    use super::{line_col, render_with};
+
+    const SOURCE: &str = "contract A {\n    uint x;\n    uint x;\n}\n";
+
+    #[test]
+    fn test_line_col() {
+        assert_eq!(line_col(SOURCE, 0), (1, 1));
+        assert_eq!(line_col(SOURCE, 17), (2, 5));
+        assert_eq!(line_col(SOURCE, 29), (3, 5));
+    }
+
+    #[test]
+    fn test_render() {
+        let error: OutputError = serde_json::from_str(
+            r#"{
+                "sourceLocation": { "file": "A.sol", "start": 29, "end": 36 },
+                "type": "DeclarationError",
+                "component": "general",
+                "severity": "error",
+                "errorCode": "2333",
+                "message": "Identifier already declared.",
+                "formattedMessage": ""
+            }"#,
+        )
+        .unwrap();
+
+        let error = OutputError {
+            secondary_source_locations: vec![SecondarySourceLocations {
+                location: SourceLocation {
+                    file: "A.sol".to_string(),
+                    start: 17,
+                    end: 24,
+                },
+                message: "The previous declaration is here:".to_string(),
+            }],
+            ..error
+        };
+
+        let rendered = render_with(&error, |_| Some(SOURCE));
+
+        assert_eq!(
+            rendered,
+            "error[2333]: Identifier already declared.\n \
+             --> A.sol:3:5\n  |\n3 |     uint x;\n  |     ^^^^^^^\n\
+             note: The previous declaration is here:\n \
+             --> A.sol:2:5\n  |\n2 |     uint x;\n  |     ^^^^^^^\n"
+        );
+    }
+}
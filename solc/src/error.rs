@@ -1,5 +1,11 @@
+use std::path::PathBuf;
+
 use thiserror::Error;
 
+use snapper_core::EvmVersion;
+
+use crate::output::OutputError;
+
 /// Error
 #[derive(Debug, Error)]
 pub enum Error {
@@ -18,9 +24,38 @@ pub enum Error {
     #[error("Unknown Profile Type")]
     UnknownProfileType,
 
+    #[error("EVM version `{evm_version}` requires solc {required} or later, but solc {version} is selected")]
+    UnsupportedEvmVersion {
+        evm_version: EvmVersion,
+        required: String,
+        version: String,
+    },
+
     #[error("No contract output")]
     NoContractOutput,
 
+    #[error("Checksum mismatch for {}: expected {expected}, got {actual}", path.display())]
+    ChecksumMismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+
+    #[error(
+        "Unknown network `{name}` of `SNAPPER_NETWORK`, configured networks: {}",
+        if .configured.is_empty() { "none".to_string() } else { .configured.join(", ") }
+    )]
+    UnknownNetwork {
+        name: String,
+        configured: Vec<String>,
+    },
+
+    #[error(transparent)]
+    LoadError(#[from] snapper_core::LoadError),
+
+    #[error("Solidity compilation failed with {} error(s)", .0.len())]
+    CompilationFailed(Vec<OutputError>),
+
     #[error(transparent)]
     SerdeJsonError(#[from] serde_json::Error),
 
@@ -8,4 +8,22 @@ pub use api::*;
 mod solc;
 pub use solc::*;
 
+pub mod sources;
+#[doc(inline)]
+pub use sources::Sources;
+
 pub mod utils;
+
+pub mod diagnostics;
+
+pub mod cache;
+
+pub mod pragma;
+
+pub mod artifacts;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+mod error;
+pub use error::*;
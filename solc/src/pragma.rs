@@ -0,0 +1,223 @@
+//! Resolve compiler version from `pragma solidity`
+
+use anyhow::{anyhow, Result};
+use semver::{Version, VersionReq};
+
+use crate::{sources::keyword_statements, version::Platform, CompilerVersions, Sources};
+
+/// Version requirement written as solidity pragma, like `>=0.6.0 <0.9.0 || ^0.5.0`.
+#[derive(Debug, Clone)]
+pub struct Requirement {
+    pub text: String,
+    alternatives: Vec<VersionReq>,
+}
+
+impl Requirement {
+    pub fn parse(text: &str) -> Result<Self> {
+        let alternatives = text
+            .split("||")
+            .map(parse_range)
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| anyhow!("Invalid version requirement `{text}`: {e}"))?;
+
+        Ok(Self {
+            text: text.trim().to_string(),
+            alternatives,
+        })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.alternatives.iter().any(|r| r.matches(version))
+    }
+}
+
+/// Convert npm style range into semver requirement.
+///
+/// Solidity treats bare version as exact, comparators are separated by
+/// whitespace and `a - b` means `>=a <=b`.
+fn parse_range(range: &str) -> Result<VersionReq> {
+    let mut comparators: Vec<String> = Vec::new();
+    let mut op = String::new();
+    let mut tokens = range.split_whitespace().peekable();
+
+    while let Some(token) = tokens.next() {
+        let split = token
+            .find(|c: char| !matches!(c, '<' | '>' | '=' | '^' | '~'))
+            .unwrap_or(token.len());
+
+        op.push_str(&token[..split]);
+        let version = &token[split..];
+
+        if version.is_empty() {
+            continue;
+        }
+
+        if tokens.peek() == Some(&"-") {
+            tokens.next();
+            let upper = tokens.next().ok_or(anyhow!("Missing upper bound"))?;
+            comparators.push(format!(">={version}"));
+            comparators.push(format!("<={upper}"));
+        } else if op.is_empty() && !version.contains(['*', 'x', 'X']) {
+            comparators.push(format!("={version}"));
+        } else {
+            comparators.push(format!("{op}{version}"));
+        }
+
+        op.clear();
+    }
+
+    if comparators.is_empty() {
+        return Ok(VersionReq::STAR);
+    }
+
+    Ok(VersionReq::parse(&comparators.join(", "))?)
+}
+
+/// Version requirement in `pragma solidity` of a source file.
+pub fn parse_pragma(content: &str) -> Option<String> {
+    keyword_statements(content, "pragma")
+        .into_iter()
+        .find_map(|s| s.strip_prefix("solidity").map(|s| s.trim().to_string()))
+}
+
+/// Pick compiler version for `sources` from registry.
+///
+/// `spec` is an exact version, `auto` or a version range. The newest build
+/// satisfying `spec` and all pragmas is selected.
+pub fn resolve_version(
+    spec: &str,
+    sources: &Sources,
+    versions: &CompilerVersions,
+    platform: &Platform,
+) -> Result<String> {
+    let available = versions.available(platform);
+    select_version(spec, sources, &available, platform.to_str())
+}
+
+/// Pick platform and compiler version for `sources` from registry.
+///
+/// Native build is preferred, fallback to wasm build when no native build
+/// matches.
+pub fn resolve_build(
+    spec: &str,
+    sources: &Sources,
+    versions: &CompilerVersions,
+) -> Result<(Platform, String)> {
+    let mut error = None;
+
+    for platform in Platform::candidates() {
+        match resolve_version(spec, sources, versions, &platform) {
+            Ok(version) => return Ok((platform, version)),
+            Err(e) => {
+                error.get_or_insert(e);
+            }
+        }
+    }
+
+    Err(error.unwrap_or(anyhow!("No support platform")))
+}
+
+/// Pick compiler version for `sources` from `available` versions.
+///
+/// `target` names where versions come from, used in error messages.
+pub fn select_version(
+    spec: &str,
+    sources: &Sources,
+    available: &[Version],
+    target: &str,
+) -> Result<String> {
+    let mut requirements = Vec::new();
+
+    if Version::parse(spec).is_ok() {
+        let r = Requirement::parse(&format!("={spec}"))?;
+        requirements.push(("Snapper.toml".to_string(), r));
+    } else if spec != "auto" {
+        requirements.push(("Snapper.toml".to_string(), Requirement::parse(spec)?));
+    }
+
+    for (name, unit) in sources.iter() {
+        if let Some(pragma) = parse_pragma(&unit.content) {
+            requirements.push((name.clone(), Requirement::parse(&pragma)?));
+        }
+    }
+
+    let mut available = available.to_vec();
+    available.sort();
+
+    if let Some(version) = available
+        .iter()
+        .rev()
+        .find(|v| requirements.iter().all(|(_, r)| r.matches(v)))
+    {
+        return Ok(version.to_string());
+    }
+
+    let satisfiable =
+        |rs: &[&Requirement]| available.iter().any(|v| rs.iter().all(|r| r.matches(v)));
+
+    for (name, r) in &requirements {
+        if !satisfiable(&[r]) {
+            return Err(anyhow!(
+                "No available solc for {target} satisfies `{}` required by `{name}`",
+                r.text
+            ));
+        }
+    }
+
+    for (i, (a, ra)) in requirements.iter().enumerate() {
+        for (b, rb) in &requirements[i + 1..] {
+            if !satisfiable(&[ra, rb]) {
+                return Err(anyhow!(
+                    "Conflicting solc versions: `{a}` requires `{}` but `{b}` requires `{}`",
+                    ra.text,
+                    rb.text
+                ));
+            }
+        }
+    }
+
+    let files: Vec<String> = requirements
+        .iter()
+        .map(|(name, r)| format!("`{name}` ({})", r.text))
+        .collect();
+
+    Err(anyhow!(
+        "No solc version satisfies all of {}",
+        files.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use semver::Version;
+
+    use super::{parse_pragma, Requirement};
+
+    #[test]
+    fn test_parse_pragma() {
+        let content = "// SPDX-License-Identifier: MIT\npragma abicoder v2;\npragma solidity >=0.6.0 <0.9.0;\n";
+        assert_eq!(parse_pragma(content).unwrap(), ">=0.6.0 <0.9.0");
+    }
+
+    #[test]
+    fn test_requirement() {
+        let v = |s| Version::parse(s).unwrap();
+
+        let r = Requirement::parse("^0.8.9").unwrap();
+        assert!(r.matches(&v("0.8.17")));
+        assert!(!r.matches(&v("0.9.0")));
+
+        let r = Requirement::parse("0.8.17").unwrap();
+        assert!(r.matches(&v("0.8.17")));
+        assert!(!r.matches(&v("0.8.18")));
+
+        let r = Requirement::parse(">= 0.6.0 < 0.8.0 || ^0.8.17").unwrap();
+        assert!(r.matches(&v("0.7.6")));
+        assert!(!r.matches(&v("0.8.0")));
+        assert!(r.matches(&v("0.8.20")));
+
+        let r = Requirement::parse("0.5.0 - 0.6.12").unwrap();
+        assert!(r.matches(&v("0.6.12")));
+        assert!(!r.matches(&v("0.7.0")));
+    }
+}
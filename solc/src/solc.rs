@@ -1,100 +1,172 @@
 use std::{
-    collections::HashMap,
-    fs::{self, File},
+    collections::BTreeMap,
+    fs,
     io::Write,
     path::{Path, PathBuf},
     process::{Command, Stdio},
 };
 
 use anyhow::{anyhow, Result};
-use snapper_core::{ProfileType, SnapperFile};
+use snapper_core::{EvmVersion, ProfileType, SnapperFile};
+
+use serde_json::Value;
 
 use crate::{
-    input::{
-        self, DebugInfo, Optimizer, OptimizerDetails, OutputSelection, RevertStrings,
-        SettingsDebug, SourceFile, YulDetails,
-    },
+    artifacts,
+    cache::{sha256_hex, CompilerCache},
+    diagnostics,
+    input::{self, Metadata, Optimizer, OptimizerDetails, SettingsDebug, SourceFile, YulDetails},
+    output::OutputError,
+    pragma,
+    sources::artifact_dir,
     utils,
     version::Platform,
-    CompilerInput, CompilerOutput, CompilerVersions,
+    CompilerInput, CompilerOutput, CompilerVersions, Error, Sources,
 };
 
 pub struct Solc {
     pub snapper: SnapperFile,
+    pub version: String,
+    /// Name of compilation job, used to keep cache apart.
+    pub name: String,
     solc_path: PathBuf,
+    /// Network to link libraries for, default from `SNAPPER_NETWORK`.
+    pub network: Option<String>,
+    /// `solc_path` is `soljson.js` run in wasm interpreter.
+    wasm: bool,
 }
 
 impl Solc {
     /// New a solc instance
     ///
+    /// Compiler version is resolved from `pragma solidity` of `sources` when
+    /// `solidity.version` is `auto` or a range.
+    ///
     /// Notice: this function only can be call in `build.rs`
-    pub fn new<P: AsRef<Path>>(out_dir: P, upstream: Option<&str>, snapper: &str) -> Result<Self> {
+    pub fn new<P: AsRef<Path>>(
+        out_dir: P,
+        upstream: Option<&str>,
+        snapper: SnapperFile,
+        sources: &Sources,
+    ) -> Result<Self> {
         let versions = if let Some(upstream) = upstream {
             CompilerVersions::load_from(upstream)?
         } else {
-            CompilerVersions::load()?
+            CompilerVersions::load_for(&snapper)?
         };
 
-        let snapper = utils::load_snapper_file(snapper)?;
+        Self::with_versions(out_dir, &versions, snapper, sources)
+    }
+
+    /// New a solc instance with loaded version infomations.
+    ///
+    /// Use `solidity.solc_path` if set, otherwise binary in `out_dir`, which
+    /// is downloaded when missing. Native build is preferred, `soljson.js` is
+    /// used when no native build matches.
+    pub fn with_versions<P: AsRef<Path>>(
+        out_dir: P,
+        versions: &CompilerVersions,
+        snapper: SnapperFile,
+        sources: &Sources,
+    ) -> Result<Self> {
+        let spec = &snapper.solidity.version;
+
+        if let Some(solc_path) = &snapper.solidity.solc_path {
+            let solc_path = PathBuf::from(solc_path);
+
+            let version = utils::solc_version(&solc_path)?;
+            let available = [semver::Version::parse(&version)?];
+            let target = solc_path.display().to_string();
+            pragma::select_version(spec, sources, &available, &target)?;
+
+            return Ok(Self {
+                snapper,
+                version,
+                name: "default".to_string(),
+                solc_path,
+                network: utils::active_network(),
+                wasm: false,
+            });
+        }
 
-        let platform = Platform::from_target().ok_or(anyhow!("No support platform"))?;
+        let (platform, version) = pragma::resolve_build(spec, sources, versions)?;
 
         fs::create_dir_all(&out_dir)?;
 
-        let solc_path = utils::solc_path(out_dir.as_ref(), &snapper.solidity.version)?;
+        let wasm = platform == Platform::Wasm;
 
-        if !solc_path.exists() {
-            versions.download(&snapper.solidity.version, &platform, &solc_path)?;
+        let solc_path = if wasm {
+            utils::soljson_path(out_dir.as_ref(), &version)?
+        } else {
+            utils::solc_path(out_dir.as_ref(), &version)?
+        };
+
+        if solc_path.exists() {
+            versions.verify(&version, &platform, &solc_path)?;
+        } else if utils::is_offline(&snapper) {
+            return Err(anyhow!(
+                "solc {version} not found at {} in offline mode",
+                solc_path.display()
+            ));
+        } else {
+            versions.download(&version, &platform, &solc_path)?;
         }
 
-        Ok(Self { snapper, solc_path })
+        Ok(Self {
+            snapper,
+            version,
+            name: "default".to_string(),
+            solc_path,
+            network: utils::active_network(),
+            wasm,
+        })
     }
 
-    pub fn compile<P: AsRef<Path>>(
-        &self,
-        file: P,
-        profile_type: &ProfileType,
-        out_dir: P,
-    ) -> Result<Vec<String>> {
-        let mut sources = HashMap::new();
+    /// EVM version to target, default is the default of solc version.
+    ///
+    /// Error if solc version doesn't support configured EVM version.
+    pub fn evm_version(&self) -> Result<Option<EvmVersion>> {
+        let version = semver::Version::parse(&self.version)?;
+        let version = (version.major, version.minor, version.patch);
+
+        match self.snapper.solidity.evm_version {
+            Some(evm_version) if !evm_version.is_supported_by(version) => {
+                let (major, minor, patch) = evm_version.min_solc();
+
+                Err(Error::UnsupportedEvmVersion {
+                    evm_version,
+                    required: format!("{major}.{minor}.{patch}"),
+                    version: self.version.clone(),
+                }
+                .into())
+            }
+            Some(evm_version) => Ok(Some(evm_version)),
+            None => Ok(EvmVersion::default_for(version)),
+        }
+    }
 
-        let file = file.as_ref();
+    /// Compiler settings of profile, select `[solidity.outputs]` of all
+    /// source units.
+    pub fn settings(&self, profile_type: &ProfileType) -> Result<input::Settings> {
+        let mut output_selection = BTreeMap::new();
 
-        let filename = file
-            .file_name()
-            .ok_or(anyhow!("Failed to parse filename"))?
-            .to_string_lossy()
-            .to_string();
+        let (selection, _) = artifacts::selected(&self.snapper.solidity.outputs);
 
-        let sf = SourceFile {
-            keccak256: None,
-            urls: vec![file.to_string_lossy().to_string()],
-        };
-        sources.insert(filename.clone(), sf);
-
-        let mut output_selection = HashMap::new();
-
-        let mut contract_output = HashMap::new();
-        contract_output.insert(
-            "*".to_string(),
-            vec![
-                OutputSelection::Abi,
-                OutputSelection::EvmBytecode,
-                OutputSelection::EvmGasEstimates,
-                OutputSelection::EvmBytecodeSourceMap,
-                OutputSelection::EvmDeployedBytecode,
-            ],
-        );
+        let mut contract_output = BTreeMap::new();
+        contract_output.insert("*".to_string(), selection);
 
         output_selection.insert("*".to_string(), contract_output);
 
-        let profile = self.snapper.get_solidity_profile(profile_type);
+        let profile = self
+            .snapper
+            .get_solidity_profile(profile_type)
+            .map_err(Error::from)?;
 
-        let revert_strings = if profile.debug {
-            RevertStrings::Debug
-        } else {
-            RevertStrings::Default
-        };
+        let metadata = (!profile.metadata.is_empty()).then(|| Metadata {
+            append_cbor: profile.metadata.append_cbor,
+            use_literal_content: profile.metadata.use_literal_content,
+            bytecode_hash: profile.metadata.bytecode_hash.clone(),
+        });
 
         let optimizer = Optimizer {
             enabled: profile.optimizer.enable,
@@ -105,110 +177,246 @@ impl Solc {
                 inliner: profile.optimizer.inliner,
                 jumpdest_remover: profile.optimizer.remove_jumpdest,
                 constant_optimizer: profile.optimizer.constant,
-                order_literals: false,
-                peephole: true,
-                yul: true,
+                order_literals: profile.optimizer.order_literals,
+                peephole: profile.optimizer.peephole,
+                yul: profile.optimizer.yul,
                 yul_details: YulDetails {
                     stack_allocation: profile.optimizer.yul_stack,
-                    optimizer_steps: None,
+                    optimizer_steps: profile.optimizer.yul_steps.clone(),
                 },
             },
         };
 
+        Ok(input::Settings {
+            stop_after: None,
+            remappings: self
+                .snapper
+                .solidity
+                .remappings
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect(),
+            optimizer,
+            evm_version: self.evm_version()?,
+            via_ir: self.snapper.solidity.via_ir,
+            debug: SettingsDebug {
+                revert_strings: profile.revert_strings,
+                debug_info: profile.debug_info,
+            },
+            metadata,
+            // Linked after compilation, so addresses can differ by network.
+            libraries: BTreeMap::new(),
+            output_selection,
+            model_checker: None,
+        })
+    }
+
+    /// Compile all changed source units in one job.
+    ///
+    /// Unchanged source units are skipped based on cache in `out_dir`.
+    /// Bytecode is linked with libraries of active network, and bytecode of
+    /// each network with `[networks.<name>.library]` is written under
+    /// `artifacts::network_dir`.
+    /// Return names of contracts in each source unit.
+    pub fn compile<P: AsRef<Path>>(
+        &self,
+        sources: &Sources,
+        profile_type: &ProfileType,
+        out_dir: P,
+    ) -> Result<BTreeMap<String, Vec<String>>> {
+        let out_dir = out_dir.as_ref();
+
+        let mut settings = self.settings(profile_type)?;
+
+        if let Some(name) = &self.network {
+            if !self.snapper.networks.contains_key(name) {
+                return Err(Error::UnknownNetwork {
+                    name: name.clone(),
+                    configured: self.snapper.networks.keys().cloned().collect(),
+                }
+                .into());
+            }
+        }
+
+        let libraries = self
+            .snapper
+            .libraries(self.network.as_deref())
+            .map_err(Error::from)?;
+
+        let mut networks = BTreeMap::new();
+        for (name, network) in &self.snapper.networks {
+            if !network.library.is_empty() {
+                let libraries = self.snapper.libraries(Some(name)).map_err(Error::from)?;
+                networks.insert(name.clone(), libraries);
+            }
+        }
+
+        let settings_hash = sha256_hex(&serde_json::to_vec(&(&settings, &libraries, &networks))?);
+
+        let mut cache = CompilerCache::load(out_dir, &self.name);
+        let dirty = cache.dirty(sources, &self.version, &settings_hash, out_dir);
+
+        if dirty.is_empty() {
+            return Ok(cache.contracts(sources));
+        }
+
+        // Only select output of changed units, imports are compiled as needed.
+        let selection = settings.output_selection.remove("*").unwrap_or_default();
+        for name in &dirty {
+            settings
+                .output_selection
+                .insert(name.clone(), selection.clone());
+        }
+
+        let mut input_sources = BTreeMap::new();
+
+        for name in sources.closure(&dirty) {
+            if let Some(unit) = sources.get(&name) {
+                let sf = SourceFile {
+                    keccak256: None,
+                    urls: vec![],
+                    content: Some(unit.content.clone()),
+                };
+                input_sources.insert(name, sf);
+            }
+        }
+
         let input = CompilerInput {
             language: input::InputLanguage::Solidity,
-            sources,
-            settings: input::Settings {
-                stop_after: None,
-                remappings: vec![],
-                optimizer,
-                evm_version: self.snapper.solidity.evm_version.clone(),
-                via_ir: self.snapper.solidity.via_ir,
-                debug: SettingsDebug {
-                    revert_strings,
-                    debug_info: vec![DebugInfo::All],
-                },
-                metadata: None,
-                libraries: self.snapper.library.clone(),
-                output_selection,
-                model_checker: None,
-            },
+            sources: input_sources,
+            settings,
         };
 
-        let in_data = serde_json::to_string(&input)?;
+        let output = self.run_json(&input, sources)?;
 
-        let mut command = Command::new(self.solc_path.clone())
-            .arg("--standard-json")
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .spawn()?;
+        let (_, artifacts) = artifacts::selected(&self.snapper.solidity.outputs);
+        let bytecodes: Vec<_> = artifacts
+            .iter()
+            .filter(|a| matches!(a.format, artifacts::Format::Hex))
+            .copied()
+            .collect();
 
-        command
-            .stdin
-            .take()
-            .ok_or(anyhow!("Failed to get stdin"))?
-            .write_all(in_data.as_bytes())?;
+        let mut res: BTreeMap<String, Vec<String>> =
+            dirty.iter().map(|n| (n.clone(), vec![])).collect();
 
-        let output = command.wait_with_output()?;
+        let output_contracts = output
+            .get("contracts")
+            .and_then(|c| c.as_object())
+            .ok_or(anyhow!("No target contract output"))?;
 
-        let res: CompilerOutput = serde_json::from_slice(&output.stdout)?;
+        for (source, contracts) in output_contracts {
+            let contracts = contracts.as_object().cloned().unwrap_or_default();
 
-        if !res.errors.is_empty() {
-            println!("{:?}", res.errors);
-            panic!("Solidity compile error");
-        }
+            let contract_dir = artifact_dir(out_dir, source);
+            fs::create_dir_all(&contract_dir)?;
 
-        let res = if let Some(contracts) = res
-            .contracts
-            .ok_or(anyhow!("No target contract output"))?
-            .get(&filename)
-        {
-            let mut res = Vec::with_capacity(contracts.len());
+            let mut names = Vec::with_capacity(contracts.len());
 
             for (name, contract) in contracts.iter() {
-                res.push(name.clone());
+                names.push(name.clone());
 
-                let out_dir = out_dir.as_ref();
+                artifacts::write(&contract_dir, name, contract, &artifacts, &libraries)?;
 
-                let contract_dir = out_dir.join(&filename);
-                fs::create_dir_all(&contract_dir)?;
+                for (network, libraries) in &networks {
+                    let dir = artifact_dir(&artifacts::network_dir(out_dir, network), source);
+                    fs::create_dir_all(&dir)?;
 
-                let abi = &contract.abi;
-                let bytecode = &contract.evm.bytecode.object;
-                let opcodes = &contract.evm.bytecode.opcodes.trim();
-                let sourcemap = &contract.evm.bytecode.source_map.trim();
-                let gas = &contract.evm.gas_estimates;
+                    artifacts::write(&dir, name, contract, &bytecodes, libraries)?;
+                }
+            }
 
-                let mut file = File::create(contract_dir.join(format!("{name}.abi.json")))?;
-                file.write_all(serde_json::to_string(abi)?.as_bytes())?;
+            names.sort();
+            res.insert(source.clone(), names);
+        }
 
-                let mut file = File::create(contract_dir.join(format!("{name}.bytecode")))?;
-                file.write_all(bytecode)?;
+        cache.update(sources, &self.version, &settings_hash, &res);
+        cache.save(out_dir, &self.name)?;
 
-                let mut file = File::create(contract_dir.join(format!("{name}.opcodes")))?;
-                file.write_all(opcodes.as_bytes())?;
+        Ok(cache.contracts(sources))
+    }
 
-                let mut file = File::create(contract_dir.join(format!("{name}.gas.json")))?;
-                file.write_all(serde_json::to_string(gas)?.as_bytes())?;
+    /// Run solc with standard json input, report diagnostics.
+    pub fn run(&self, input: &CompilerInput, sources: &Sources) -> Result<CompilerOutput> {
+        Ok(serde_json::from_value(self.run_json(input, sources)?)?)
+    }
 
-                let mut file = File::create(contract_dir.join(format!("{name}.sourcemap")))?;
-                file.write_all(sourcemap.as_bytes())?;
-            }
+    /// Run solc with standard json input, report diagnostics and return
+    /// output as json without `errors`.
+    pub fn run_json(&self, input: &CompilerInput, sources: &Sources) -> Result<Value> {
+        let in_data = serde_json::to_string(input)?;
 
-            res
+        let stdout = if self.wasm {
+            self.run_wasm(&in_data)?
         } else {
-            vec![]
+            self.run_native(&in_data)?
         };
 
-        Ok(res)
+        let mut output: Value = serde_json::from_slice(&stdout)?;
+
+        let errors: Vec<OutputError> = match output.as_object_mut().and_then(|o| o.remove("errors"))
+        {
+            Some(errors) => serde_json::from_value(errors)?,
+            None => vec![],
+        };
+
+        let (errors, warnings): (Vec<_>, Vec<_>) =
+            errors.into_iter().partition(|e| e.severity.is_error());
+
+        for warning in &warnings {
+            for line in diagnostics::render(warning, sources).lines() {
+                println!("cargo:warning={line}");
+            }
+        }
+
+        if !errors.is_empty() {
+            for error in &errors {
+                eprintln!("{}", diagnostics::render(error, sources));
+            }
+
+            return Err(Error::CompilationFailed(errors).into());
+        }
+
+        Ok(output)
+    }
+
+    fn run_native(&self, in_data: &str) -> Result<Vec<u8>> {
+        // Sources are passed by content, base and include paths are only
+        // used to resolve imports in `Sources`.
+        let mut command = Command::new(self.solc_path.clone())
+            .arg("--standard-json")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        command
+            .stdin
+            .take()
+            .ok_or(anyhow!("Failed to get stdin"))?
+            .write_all(in_data.as_bytes())?;
+
+        let output = command.wait_with_output()?;
+
+        Ok(output.stdout)
+    }
+
+    #[cfg(feature = "wasm")]
+    fn run_wasm(&self, in_data: &str) -> Result<Vec<u8>> {
+        Ok(crate::wasm::compile(&self.solc_path, in_data)?.into_bytes())
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    fn run_wasm(&self, _in_data: &str) -> Result<Vec<u8>> {
+        Err(anyhow!("Running soljson requires `wasm` feature"))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use snapper_core::ProfileType;
+    use std::path::PathBuf;
 
-    use crate::Solc;
+    use snapper_core::{EvmVersion, ProfileType};
+
+    use crate::{utils, Solc, Sources};
 
     #[test]
     fn test() {
@@ -216,13 +424,69 @@ mod tests {
         let snapper_file = "../cargo-snapper/assets/Snapper.toml";
 
         let sf = std::fs::read_to_string(snapper_file).unwrap();
+        let sf = utils::load_snapper_file(&sf).unwrap();
+
+        let sources = Sources::collect("contracts").unwrap();
+        let solc = Solc::new(out_dir, None, sf, &sources).unwrap();
+        solc.compile(&sources, &ProfileType::Debug, "../target/solc-test/")
+            .unwrap();
+    }
 
-        let solc = Solc::new(out_dir, None, &sf).unwrap();
-        solc.compile(
-            "contracts/Lock.sol",
-            &ProfileType::Debug,
-            "../target/solc-test/",
-        )
-        .unwrap();
+    #[test]
+    fn test_evm_version() {
+        let sf = "[project]\n[networks]\n[solidity]\nversion = \"0.8.17\"\n";
+        let sf = utils::load_snapper_file(sf).unwrap();
+
+        let mut solc = Solc {
+            snapper: sf,
+            version: "0.8.17".to_string(),
+            name: "default".to_string(),
+            solc_path: PathBuf::from("solc"),
+            network: None,
+            wasm: false,
+        };
+
+        assert_eq!(solc.evm_version().unwrap(), Some(EvmVersion::London));
+
+        solc.version = "0.8.25".to_string();
+        assert_eq!(solc.evm_version().unwrap(), Some(EvmVersion::Cancun));
+
+        solc.version = "0.4.11".to_string();
+        assert_eq!(solc.evm_version().unwrap(), None);
+
+        solc.version = "0.8.19".to_string();
+        solc.snapper.solidity.evm_version = Some(EvmVersion::Shanghai);
+        assert_eq!(
+            solc.evm_version().unwrap_err().to_string(),
+            "EVM version `shanghai` requires solc 0.8.20 or later, but solc 0.8.19 is selected"
+        );
+
+        solc.version = "0.8.20".to_string();
+        assert_eq!(solc.evm_version().unwrap(), Some(EvmVersion::Shanghai));
+    }
+
+    #[test]
+    fn test_unknown_network() {
+        let sf = "[project]\n[solidity]\nversion = \"0.8.17\"\n\
+                  [networks.localhost]\nurl = \"http://127.0.0.1:8545/\"\naccounts = []\n";
+        let sf = utils::load_snapper_file(sf).unwrap();
+
+        let solc = Solc {
+            snapper: sf,
+            version: "0.8.17".to_string(),
+            name: "default".to_string(),
+            solc_path: PathBuf::from("solc"),
+            network: Some("goerli".to_string()),
+            wasm: false,
+        };
+
+        let sources = Sources::new("contracts");
+        let err = solc
+            .compile(&sources, &ProfileType::Debug, "../target/solc-test/")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown network `goerli` of `SNAPPER_NETWORK`, configured networks: localhost"
+        );
     }
 }
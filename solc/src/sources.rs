@@ -0,0 +1,477 @@
+//! Source units of a compilation job
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+
+/// A single solidity source file loaded from disk.
+#[derive(Debug, Clone)]
+pub struct SourceUnit {
+    /// Path of file on disk.
+    pub path: PathBuf,
+    pub content: String,
+    /// Resolved source unit names of all imports.
+    pub imports: Vec<String>,
+}
+
+/// All source units of a project, keyed by source unit name.
+///
+/// Source unit names are paths relative to `base_path`, using `/` as separator.
+#[derive(Debug, Clone, Default)]
+pub struct Sources {
+    base_path: PathBuf,
+    include_paths: Vec<PathBuf>,
+    remappings: Vec<Remapping>,
+    units: BTreeMap<String, SourceUnit>,
+    /// Units to output, `None` means all.
+    roots: Option<BTreeSet<String>>,
+}
+
+#[derive(Debug, Clone)]
+struct Remapping {
+    context: String,
+    prefix: String,
+    target: String,
+}
+
+impl Sources {
+    pub fn new<P: AsRef<Path>>(base_path: P) -> Self {
+        Self {
+            base_path: base_path.as_ref().to_path_buf(),
+            ..Default::default()
+        }
+    }
+
+    /// Collect all `.sol` files under `base_path` and their imports.
+    pub fn collect<P: AsRef<Path>>(base_path: P) -> Result<Self> {
+        let mut sources = Self::new(&base_path);
+        sources.add_dir(base_path)?;
+        Ok(sources)
+    }
+
+    pub fn base_path(&self) -> &Path {
+        &self.base_path
+    }
+
+    /// Add a directory to search for imported files after `base_path`.
+    pub fn include_path<P: AsRef<Path>>(&mut self, p: P) -> &mut Self {
+        self.include_paths.push(p.as_ref().to_path_buf());
+        self
+    }
+
+    /// Add an import remapping.
+    ///
+    /// `prefix` can be `context:prefix`, same as solc.
+    pub fn remapping(&mut self, prefix: &str, target: &str) -> &mut Self {
+        let (context, prefix) = match prefix.split_once(':') {
+            Some((c, p)) => (c.to_string(), p.to_string()),
+            None => (String::new(), prefix.to_string()),
+        };
+
+        self.remappings.push(Remapping {
+            context,
+            prefix,
+            target: target.to_string(),
+        });
+        self
+    }
+
+    /// Apply remappings to the source unit name imported by `importer`.
+    ///
+    /// Longest context wins, then longest prefix.
+    pub fn remap(&self, importer: &str, name: &str) -> String {
+        let remapping = self
+            .remappings
+            .iter()
+            .filter(|r| importer.starts_with(&r.context) && name.starts_with(&r.prefix))
+            .max_by_key(|r| (r.context.len(), r.prefix.len()));
+
+        match remapping {
+            Some(r) => format!("{}{}", r.target, &name[r.prefix.len()..]),
+            None => name.to_string(),
+        }
+    }
+
+    /// Find source unit on disk, search in `base_path` then include paths.
+    fn locate(&self, name: &str) -> Option<PathBuf> {
+        std::iter::once(&self.base_path)
+            .chain(self.include_paths.iter())
+            .map(|p| p.join(name))
+            .find(|p| p.is_file())
+    }
+
+    /// Add all `.sol` files under `dir` and their imports.
+    pub fn add_dir<P: AsRef<Path>>(&mut self, dir: P) -> Result<()> {
+        let dir = dir.as_ref();
+
+        if dir.is_dir() {
+            for entry in fs::read_dir(dir)? {
+                let path = entry?.path();
+
+                if path.is_dir() {
+                    self.add_dir(&path)?;
+                } else if path.extension().map(|e| e == "sol").unwrap_or(false) {
+                    self.add_file(&path)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add a file on disk and its imports.
+    pub fn add_file<P: AsRef<Path>>(&mut self, file: P) -> Result<()> {
+        let file = file.as_ref();
+
+        let name = std::iter::once(&self.base_path)
+            .chain(self.include_paths.iter())
+            .find_map(|p| file.strip_prefix(p).ok())
+            .ok_or_else(|| {
+                anyhow!(
+                    "{} is outside of base path {} and include paths",
+                    file.display(),
+                    self.base_path.display()
+                )
+            })?;
+
+        self.add(&normalize(&name.to_string_lossy()))
+    }
+
+    /// Add a source unit by name and its imports.
+    pub fn add(&mut self, name: &str) -> Result<()> {
+        let mut pending = vec![(name.to_string(), None::<String>)];
+
+        while let Some((name, importer)) = pending.pop() {
+            if self.units.contains_key(&name) {
+                continue;
+            }
+
+            let path = self.locate(&name).ok_or_else(|| match &importer {
+                Some(importer) => anyhow!("Can't find `{name}` imported by `{importer}`"),
+                None => anyhow!("Can't find `{name}`"),
+            })?;
+
+            let content = fs::read_to_string(&path)
+                .map_err(|e| anyhow!("Failed to read {}: {e}", path.display()))?;
+
+            let imports: Vec<String> = parse_imports(&content)
+                .iter()
+                .map(|i| self.remap(&name, &resolve_import(&name, i)))
+                .collect();
+
+            for import in &imports {
+                pending.push((import.clone(), Some(name.clone())));
+            }
+
+            let unit = SourceUnit {
+                path,
+                content,
+                imports,
+            };
+
+            self.units.insert(name, unit);
+        }
+
+        Ok(())
+    }
+
+    /// Units in closure of `roots`, only `roots` are output when compiled.
+    pub fn subset<'a, I>(&self, roots: I) -> Self
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let roots: BTreeSet<String> = roots.into_iter().cloned().collect();
+
+        let units = self
+            .closure(&roots)
+            .into_iter()
+            .filter_map(|name| self.units.get(&name).map(|u| (name, u.clone())))
+            .collect();
+
+        Self {
+            base_path: self.base_path.clone(),
+            include_paths: self.include_paths.clone(),
+            remappings: self.remappings.clone(),
+            units,
+            roots: Some(roots),
+        }
+    }
+
+    /// Names of units to output.
+    pub fn roots(&self) -> impl Iterator<Item = &String> {
+        self.units.keys().filter(|name| {
+            self.roots
+                .as_ref()
+                .map(|r| r.contains(*name))
+                .unwrap_or(true)
+        })
+    }
+
+    pub fn is_root(&self, name: &str) -> bool {
+        self.units.contains_key(name)
+            && self
+                .roots
+                .as_ref()
+                .map(|r| r.contains(name))
+                .unwrap_or(true)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SourceUnit> {
+        self.units.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &SourceUnit)> {
+        self.units.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.units.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    /// Names of `roots` and all units they import transitively.
+    pub fn closure<'a, I>(&self, roots: I) -> BTreeSet<String>
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let mut res = BTreeSet::new();
+        let mut pending: Vec<&String> = roots.into_iter().collect();
+
+        while let Some(name) = pending.pop() {
+            if res.insert(name.clone()) {
+                if let Some(unit) = self.units.get(name) {
+                    pending.extend(unit.imports.iter());
+                }
+            }
+        }
+
+        res
+    }
+}
+
+/// Resolve import path to source unit name.
+///
+/// Relative imports (`./` and `../`) are resolved against the importing unit,
+/// other imports are used verbatim.
+pub fn resolve_import(importer: &str, import: &str) -> String {
+    if import.starts_with("./") || import.starts_with("../") {
+        let dir = match importer.rfind('/') {
+            Some(pos) => &importer[..pos],
+            None => "",
+        };
+
+        normalize(&format!("{dir}/{import}"))
+    } else {
+        normalize(import)
+    }
+}
+
+/// Normalize source unit name, remove `.` and resolve `..` where possible.
+fn normalize(name: &str) -> String {
+    let mut res: Vec<&str> = Vec::new();
+
+    for seg in name.split(['/', '\\']) {
+        match seg {
+            "" | "." => {}
+            ".." => {
+                if matches!(res.last(), Some(s) if *s != "..") {
+                    res.pop();
+                } else {
+                    res.push(seg);
+                }
+            }
+            _ => res.push(seg),
+        }
+    }
+
+    res.join("/")
+}
+
+/// Map source unit name to a directory under `out_dir`.
+pub fn artifact_dir(out_dir: &Path, source: &str) -> PathBuf {
+    let mut res = out_dir.to_path_buf();
+
+    for c in Path::new(source).components() {
+        match c {
+            Component::Normal(s) => res.push(s),
+            Component::ParentDir => res.push("_"),
+            _ => {}
+        }
+    }
+
+    res
+}
+
+/// Replace comments with whitespace, keep string literals.
+fn strip_comments(content: &str) -> String {
+    let mut res = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                res.push(c);
+                while let Some(n) = chars.next() {
+                    res.push(n);
+                    if n == '\\' {
+                        if let Some(n) = chars.next() {
+                            res.push(n);
+                        }
+                    } else if n == c {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for n in chars.by_ref() {
+                    if n == '\n' {
+                        res.push(n);
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                for n in chars.by_ref() {
+                    if n == '\n' {
+                        res.push(n);
+                    }
+                    if last == '*' && n == '/' {
+                        break;
+                    }
+                    last = n;
+                }
+                res.push(' ');
+            }
+            _ => res.push(c),
+        }
+    }
+
+    res
+}
+
+/// Body of every top level statement starting with `keyword`, without the
+/// trailing `;`.
+pub(crate) fn keyword_statements(content: &str, keyword: &str) -> Vec<String> {
+    let content = strip_comments(content);
+
+    let mut res = Vec::new();
+    let mut chars = content.char_indices().peekable();
+
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                while let Some((_, n)) = chars.next() {
+                    if n == '\\' {
+                        chars.next();
+                    } else if n == c {
+                        break;
+                    }
+                }
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '$' => {
+                let mut end = pos + c.len_utf8();
+                while let Some((p, n)) = chars.peek() {
+                    if n.is_alphanumeric() || *n == '_' || *n == '$' {
+                        end = p + n.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                if &content[pos..end] == keyword {
+                    let body = &content[end..];
+                    let body = match body.find(';') {
+                        Some(p) => &body[..p],
+                        None => body,
+                    };
+                    res.push(body.trim().to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    res
+}
+
+/// Import paths in a source file, as written.
+pub fn parse_imports(content: &str) -> Vec<String> {
+    keyword_statements(content, "import")
+        .iter()
+        .filter_map(|s| {
+            let start = s.find(['"', '\''])?;
+            let quote = s[start..].chars().next()?;
+            let end = s[start + 1..].find(quote)?;
+            Some(s[start + 1..start + 1 + end].to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_imports, resolve_import, Sources};
+
+    #[test]
+    fn test_parse_imports() {
+        let content = r#"
+            // import "commented.sol";
+            /* import "block.sol"; */
+            import "./A.sol";
+            import * as B from '../B.sol';
+            import {C, D as E} from "lib/C.sol";
+            contract X { string s = "import \"no.sol\";"; }
+        "#;
+
+        assert_eq!(
+            parse_imports(content),
+            vec!["./A.sol", "../B.sol", "lib/C.sol"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_import() {
+        assert_eq!(
+            resolve_import("token/ERC20.sol", "./IERC20.sol"),
+            "token/IERC20.sol"
+        );
+        assert_eq!(
+            resolve_import("token/ERC20.sol", "../Ownable.sol"),
+            "Ownable.sol"
+        );
+        assert_eq!(resolve_import("Lock.sol", "../lib/X.sol"), "../lib/X.sol");
+        assert_eq!(resolve_import("a/b/C.sol", "lib/D.sol"), "lib/D.sol");
+    }
+
+    #[test]
+    fn test_remap() {
+        let mut sources = Sources::new("contracts");
+        sources
+            .remapping("@openzeppelin/", "lib/openzeppelin-contracts/contracts/")
+            .remapping("@openzeppelin/token/", "lib/oz-token/")
+            .remapping("legacy:@openzeppelin/", "lib/oz-legacy/");
+
+        assert_eq!(
+            sources.remap("Token.sol", "@openzeppelin/access/Ownable.sol"),
+            "lib/openzeppelin-contracts/contracts/access/Ownable.sol"
+        );
+        assert_eq!(
+            sources.remap("Token.sol", "@openzeppelin/token/ERC20.sol"),
+            "lib/oz-token/ERC20.sol"
+        );
+        assert_eq!(
+            sources.remap("legacy/Token.sol", "@openzeppelin/token/ERC20.sol"),
+            "lib/oz-legacy/token/ERC20.sol"
+        );
+        assert_eq!(sources.remap("Token.sol", "Lock.sol"), "Lock.sol");
+    }
+}
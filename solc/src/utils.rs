@@ -1,19 +1,66 @@
 use std::{
     env,
     path::{Path, PathBuf},
+    process::Command,
 };
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use snapper_core::SnapperFile;
 
-pub fn load_snapper_file(s: &str) -> Result<SnapperFile> {
-    Ok(toml::from_str(s)?)
-}
+pub use snapper_core::{load_snapper_file, load_snapper_path, load_snapper_path_vars};
 
 pub fn solc_path(path: &Path, version: &str) -> Result<PathBuf> {
     Ok(path.join(format!("solc-v{}", version)))
 }
 
+pub fn soljson_path(path: &Path, version: &str) -> Result<PathBuf> {
+    Ok(path.join(format!("soljson-v{}.js", version)))
+}
+
+/// Offline mode, set by `solidity.offline` or `SNAPPER_OFFLINE`.
+pub fn is_offline(snapper: &SnapperFile) -> bool {
+    snapper.solidity.offline
+        || env::var("SNAPPER_OFFLINE")
+            .map(|v| !matches!(v.as_str(), "" | "0" | "false"))
+            .unwrap_or(false)
+}
+
+/// Active network set by `SNAPPER_NETWORK`, selects library addresses.
+pub fn active_network() -> Option<String> {
+    env::var("SNAPPER_NETWORK").ok().filter(|v| !v.is_empty())
+}
+
+/// Snapper home, `SNAPPER_HOME` or `~/.snapper`.
+pub fn snapper_home() -> Result<PathBuf> {
+    if let Some(home) = env::var_os("SNAPPER_HOME") {
+        return Ok(PathBuf::from(home));
+    }
+
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .ok_or(anyhow!("Failed to get home directory"))?;
+
+    Ok(Path::new(&home).join(".snapper"))
+}
+
+/// Directory of solc binaries shared by all projects.
+pub fn default_solc_dir() -> Result<PathBuf> {
+    Ok(snapper_home()?.join("solc"))
+}
+
+/// Version of solc binary at `path`, like `0.8.17`.
+pub fn solc_version(path: &Path) -> Result<String> {
+    let output = Command::new(path).arg("--version").output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    stdout
+        .lines()
+        .find_map(|l| l.strip_prefix("Version: "))
+        .and_then(|v| v.split(['+', '-']).next())
+        .map(|v| v.trim().to_string())
+        .ok_or(anyhow!("Failed to get version of {}", path.display()))
+}
+
 pub fn default_snapper_outdir() -> Result<PathBuf> {
     let target = env::var("CARGO_TARGET_DIR")?;
     let path = Path::new(&target);
@@ -1,40 +1,78 @@
 //! Version from upstream
 
-use std::{collections::HashMap, fs::OpenOptions, path::Path};
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
 
 use anyhow::{anyhow, Result};
+use semver::Version;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 
-#[derive(Debug, Serialize, Deserialize)]
+use snapper_core::SnapperFile;
+
+use crate::{utils, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Platform {
     #[serde(rename = "windows-amd64")]
     WindowsAmd64,
     #[serde(rename = "linux-amd64")]
     LinuxAmd64,
+    #[serde(rename = "linux-arm64")]
+    LinuxArm64,
     #[serde(rename = "macos-amd64")]
     MacOSAmd64,
+    #[serde(rename = "macos-arm64")]
+    MacOSArm64,
+    /// `soljson.js` with embedded wasm, run by interpreter.
+    #[serde(rename = "wasm")]
+    Wasm,
 }
 
 impl Platform {
     pub fn to_str(&self) -> &'static str {
         match self {
             Self::LinuxAmd64 => "linux-amd64",
+            Self::LinuxArm64 => "linux-arm64",
             Self::WindowsAmd64 => "windows-amd64",
             Self::MacOSAmd64 => "macos-amd64",
+            Self::MacOSArm64 => "macos-arm64",
+            Self::Wasm => "wasm",
         }
     }
 
+    /// Native platform of current target.
     pub fn from_target() -> Option<Self> {
         if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
             Some(Self::LinuxAmd64)
+        } else if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
+            Some(Self::LinuxArm64)
         } else if cfg!(all(target_os = "windows", target_arch = "x86_64")) {
             Some(Self::WindowsAmd64)
         } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
             Some(Self::MacOSAmd64)
+        } else if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+            Some(Self::MacOSArm64)
         } else {
             None
         }
     }
+
+    /// Platforms to try in order, native first then wasm fallback.
+    pub fn candidates() -> Vec<Self> {
+        let mut res: Vec<Self> = Self::from_target().into_iter().collect();
+
+        if cfg!(feature = "wasm") {
+            res.push(Self::Wasm);
+        }
+
+        res
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -53,6 +91,9 @@ pub struct CompilerVersions {
 pub const REGISTER_URL: &str =
     "https://raw.githubusercontent.com/tiannian/snapper/main/utils/solidity.json";
 
+/// Registry in `utils/solidity.json`, bundled at compile time.
+const BUNDLED_REGISTER: &str = include_str!("../../utils/solidity.json");
+
 impl CompilerVersions {
     /// Load version infomations from upstream
     ///
@@ -61,7 +102,14 @@ impl CompilerVersions {
         Self::load_from(REGISTER_URL)
     }
 
+    /// Load version infomations from `upstream`, `file://` url and local
+    /// path are read from disk.
     pub fn load_from(upstream: &str) -> Result<Self> {
+        if !is_remote(upstream) {
+            let path = upstream.strip_prefix("file://").unwrap_or(upstream);
+            return Ok(serde_json::from_slice(&fs::read(path)?)?);
+        }
+
         let response = attohttpc::get(upstream).send()?;
 
         let res = response.json()?;
@@ -69,28 +117,223 @@ impl CompilerVersions {
         Ok(res)
     }
 
+    /// Version infomations bundled in snapper.
+    pub fn bundled() -> Self {
+        serde_json::from_str(BUNDLED_REGISTER).expect("Bundled registry must be valid")
+    }
+
+    /// Load version infomations for project.
+    ///
+    /// Use `solidity.upstream` if set, otherwise the bundled registry.
+    pub fn load_for(snapper: &SnapperFile) -> Result<Self> {
+        match &snapper.solidity.upstream {
+            Some(upstream) => {
+                if is_remote(upstream) && utils::is_offline(snapper) {
+                    return Err(anyhow!(
+                        "Can't load registry from {upstream} in offline mode"
+                    ));
+                }
+
+                Self::load_from(upstream)
+            }
+            None => Ok(Self::bundled()),
+        }
+    }
+
+    /// All versions built for `platform`.
+    pub fn available(&self, platform: &Platform) -> Vec<Version> {
+        let suffix = format!("-{}", platform.to_str());
+
+        self.builds
+            .keys()
+            .filter_map(|k| k.strip_suffix(&suffix))
+            .filter_map(|v| Version::parse(v).ok())
+            .collect()
+    }
+
+    /// Artifact of `version` built for `platform`.
+    pub fn artifact(&self, version: &str, platform: &Platform) -> Result<&Artifact> {
+        self.builds
+            .get(&format!("{}-{}", version, platform.to_str()))
+            .ok_or(anyhow!("No Target support"))
+    }
+
     /// Download solc binary
+    ///
+    /// Binary is written to a temporary file and moved to `target` only when
+    /// both sha256 and keccak256 match the registry.
     pub fn download(&self, version: &str, platform: &Platform, target: &Path) -> Result<()> {
         #[cfg(unix)]
         use std::os::unix::fs::OpenOptionsExt;
 
-        let artifact = self
-            .builds
-            .get(&format!("{}-{}", version, platform.to_str()))
-            .ok_or(anyhow!("No Target support"))?;
+        let artifact = self.artifact(version, platform)?;
 
         let response = attohttpc::get(&artifact.urls[0]).send()?;
 
+        if !response.is_success() {
+            return Err(anyhow!(
+                "Failed to download {}: {}",
+                artifact.urls[0],
+                response.status()
+            ));
+        }
+
+        let mut tmp = target.as_os_str().to_owned();
+        tmp.push(format!(".{}.download", std::process::id()));
+        let tmp = PathBuf::from(tmp);
+
         let mut open = OpenOptions::new();
-        open.write(true).create(true);
+        open.write(true).create(true).truncate(true);
 
         #[cfg(unix)]
-        open.mode(0o776);
+        open.mode(0o755);
 
-        let file = open.open(target)?;
+        let file = open.open(&tmp)?;
 
-        response.write_to(file)?;
+        let mut writer = HashWriter::new(file);
+        response.write_to(&mut writer)?;
+        writer.flush()?;
+
+        if let Err(e) = writer.check(artifact, target) {
+            let _ = fs::remove_file(&tmp);
+            return Err(e.into());
+        }
+
+        fs::rename(&tmp, target)?;
 
         Ok(())
     }
+
+    /// Verify binary at `path` against the registry.
+    pub fn verify(&self, version: &str, platform: &Platform, path: &Path) -> Result<()> {
+        let artifact = self.artifact(version, platform)?;
+
+        let mut writer = HashWriter::new(io::sink());
+        io::copy(&mut File::open(path)?, &mut writer)?;
+
+        writer.check(artifact, path)?;
+
+        Ok(())
+    }
+}
+
+fn is_remote(upstream: &str) -> bool {
+    upstream.starts_with("http://") || upstream.starts_with("https://")
+}
+
+/// Hash everything written through it.
+struct HashWriter<W> {
+    inner: W,
+    sha256: Sha256,
+    keccak256: Keccak256,
+}
+
+impl<W> HashWriter<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            sha256: Sha256::new(),
+            keccak256: Keccak256::new(),
+        }
+    }
+
+    fn check(self, artifact: &Artifact, path: &Path) -> crate::Result<()> {
+        let sha256 = hex::encode(self.sha256.finalize());
+        let keccak256 = hex::encode(self.keccak256.finalize());
+
+        for (expected, actual) in [(&artifact.sha256, sha256), (&artifact.keccak256, keccak256)] {
+            let expected = expected.trim_start_matches("0x").to_lowercase();
+
+            if expected != actual {
+                return Err(Error::ChecksumMismatch {
+                    path: path.to_path_buf(),
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for HashWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.sha256.update(&buf[..n]);
+        self.keccak256.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, fs};
+
+    use sha2::{Digest, Sha256};
+    use sha3::Keccak256;
+
+    use crate::Error;
+
+    use super::{Artifact, CompilerVersions, Platform};
+
+    #[test]
+    fn test_bundled() {
+        let bundled = CompilerVersions::bundled();
+        assert!(bundled.artifact("0.8.17", &Platform::LinuxAmd64).is_ok());
+
+        let path = std::env::temp_dir().join("snapper-registry-test.json");
+        fs::write(&path, r#"{ "builds": {} }"#).unwrap();
+
+        let upstream = format!("file://{}", path.display());
+        let versions = CompilerVersions::load_from(&upstream).unwrap();
+        assert!(versions.builds.is_empty());
+    }
+
+    #[test]
+    #[ignore = "needs `utils/solidity.json` regenerated by `utils/update.sh`"]
+    fn test_bundled_platforms() {
+        let bundled = CompilerVersions::bundled();
+        assert!(bundled.artifact("0.8.17", &Platform::Wasm).is_ok());
+        // Universal binaries since 0.8.24.
+        assert!(bundled.artifact("0.8.24", &Platform::MacOSArm64).is_ok());
+        assert!(bundled.artifact("0.8.23", &Platform::MacOSArm64).is_err());
+        assert!(bundled.artifact("0.8.23", &Platform::MacOSAmd64).is_ok());
+        assert!(!bundled.available(&Platform::LinuxArm64).is_empty());
+    }
+
+    #[test]
+    fn test_verify() {
+        let path = std::env::temp_dir().join("snapper-verify-test");
+        fs::write(&path, b"solc").unwrap();
+
+        let artifact = Artifact {
+            urls: vec![],
+            sha256: format!("0x{}", hex::encode(Sha256::digest(b"solc"))),
+            keccak256: format!("0x{}", hex::encode(Keccak256::digest(b"solc"))),
+        };
+
+        let mut builds = HashMap::new();
+        builds.insert("0.8.17-linux-amd64".to_string(), artifact);
+        let versions = CompilerVersions { builds };
+
+        versions
+            .verify("0.8.17", &Platform::LinuxAmd64, &path)
+            .unwrap();
+
+        fs::write(&path, b"evil").unwrap();
+
+        let err = versions
+            .verify("0.8.17", &Platform::LinuxAmd64, &path)
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::ChecksumMismatch { .. })
+        ));
+    }
 }
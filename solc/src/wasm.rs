@@ -0,0 +1,663 @@
+//! Run `soljson.js` builds of solc in an embedded wasm interpreter
+//!
+//! Used on platforms without native solc builds. The wasm module is extracted
+//! from `soljson.js`, and imports normally provided by emscripten javascript
+//! runtime are implemented here.
+
+use std::{
+    fmt, fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Result};
+use base64::Engine as _;
+use wasmi::{
+    core::{HostError, Pages},
+    Caller, Engine, Extern, ExternType, Func, Global, Linker, Memory, Module, Store, Table, Val,
+};
+
+/// Prefix of wasm module embedded in `soljson.js`.
+const WASM_DATA_PREFIX: &str = "data:application/octet-stream;base64,";
+
+/// Size of emscripten `ExceptionInfo` placed before thrown object.
+const EXCEPTION_INFO_SIZE: i32 = 24;
+
+/// `ENOSYS` in emscripten.
+const ENOSYS: i32 = 52;
+
+/// C++ exception unwinding through host frames, caught by `invoke_*`.
+#[derive(Debug)]
+struct Throw;
+
+impl fmt::Display for Throw {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Uncaught C++ exception in soljson")
+    }
+}
+
+impl HostError for Throw {}
+
+#[derive(Default)]
+struct State {
+    memory: Option<Memory>,
+    temp_ret0: i32,
+    exception_last: i32,
+    exception_caught: Vec<i32>,
+    uncaught_exceptions: i32,
+}
+
+/// Host implementation of an import.
+#[derive(Debug, Clone)]
+enum Import {
+    /// `invoke_*`, call function in table and catch exceptions.
+    Invoke(String),
+    CxaAllocateException,
+    CxaFreeException,
+    CxaThrow,
+    CxaBeginCatch,
+    CxaEndCatch,
+    CxaRethrow,
+    CxaUncaughtExceptions,
+    FindMatchingCatch,
+    ResumeException,
+    TypeidFor,
+    SetTempRet0,
+    GetTempRet0,
+    MemcpyBig,
+    ResizeHeap,
+    Now,
+    FdWrite,
+    SizesGet,
+    ClockTimeGet,
+    ProcExit,
+    Abort,
+    Syscall,
+    /// Return zero, for functions with no observable effect in compiler.
+    Zero,
+    /// Trap when called.
+    Unsupported(String),
+}
+
+impl Import {
+    fn from_name(name: &str) -> Self {
+        let stripped = name.trim_start_matches('_');
+
+        if let Some(sig) = name.strip_prefix("invoke_") {
+            return Self::Invoke(format!("dynCall_{sig}"));
+        }
+
+        if stripped.starts_with("cxa_find_matching_catch") {
+            return Self::FindMatchingCatch;
+        }
+
+        if stripped.starts_with("syscall") {
+            return Self::Syscall;
+        }
+
+        match stripped {
+            "cxa_allocate_exception" => Self::CxaAllocateException,
+            "cxa_free_exception" => Self::CxaFreeException,
+            "cxa_throw" => Self::CxaThrow,
+            "cxa_begin_catch" => Self::CxaBeginCatch,
+            "cxa_end_catch" => Self::CxaEndCatch,
+            "cxa_rethrow" => Self::CxaRethrow,
+            "cxa_uncaught_exceptions" => Self::CxaUncaughtExceptions,
+            "resumeException" => Self::ResumeException,
+            "llvm_eh_typeid_for" => Self::TypeidFor,
+            "setTempRet0" => Self::SetTempRet0,
+            "getTempRet0" => Self::GetTempRet0,
+            "emscripten_memcpy_big" | "emscripten_memcpy_js" => Self::MemcpyBig,
+            "emscripten_resize_heap" => Self::ResizeHeap,
+            "emscripten_date_now" | "emscripten_get_now" => Self::Now,
+            "fd_write" => Self::FdWrite,
+            "environ_sizes_get" | "args_sizes_get" => Self::SizesGet,
+            "clock_time_get" => Self::ClockTimeGet,
+            "proc_exit" | "exit" => Self::ProcExit,
+            "abort" | "assert_fail" | "abort_js" | "emscripten_throw_longjmp" => Self::Abort,
+            "environ_get"
+            | "args_get"
+            | "fd_close"
+            | "fd_seek"
+            | "fd_read"
+            | "fd_sync"
+            | "fd_fdstat_get"
+            | "tzset_js"
+            | "localtime_js"
+            | "gmtime_js"
+            | "mktime_js"
+            | "timegm_js"
+            | "emscripten_notify_memory_growth"
+            | "emscripten_get_heap_max"
+            | "strftime"
+            | "strftime_l"
+            | "emscripten_stack_set_limits"
+            | "handle_stack_overflow" => Self::Zero,
+            _ => Self::Unsupported(name.to_string()),
+        }
+    }
+
+    fn call(
+        &self,
+        mut caller: Caller<'_, State>,
+        params: &[Val],
+        results: &mut [Val],
+    ) -> Result<(), wasmi::Error> {
+        let arg = |i: usize| params.get(i).and_then(Val::i32).unwrap_or_default();
+
+        let ret = match self {
+            Self::Invoke(dyn_call) => return invoke(caller, dyn_call, params, results),
+            Self::CxaAllocateException => {
+                let ptr = call_i32(&mut caller, "malloc", &[arg(0) + EXCEPTION_INFO_SIZE])?;
+                write_bytes(&mut caller, ptr, &[0; EXCEPTION_INFO_SIZE as usize])?;
+                ptr + EXCEPTION_INFO_SIZE
+            }
+            Self::CxaFreeException => {
+                call_i32(&mut caller, "free", &[arg(0) - EXCEPTION_INFO_SIZE])?;
+                0
+            }
+            Self::CxaThrow => {
+                let (ptr, ty, destructor) = (arg(0), arg(1), arg(2));
+                let info = ptr - EXCEPTION_INFO_SIZE;
+
+                write_i32(&mut caller, info + 4, ty)?;
+                write_i32(&mut caller, info + 8, destructor)?;
+                write_bytes(&mut caller, info + 12, &[0, 0])?;
+                write_i32(&mut caller, info + 16, 0)?;
+
+                let state = caller.data_mut();
+                state.exception_last = ptr;
+                state.uncaught_exceptions += 1;
+
+                return Err(wasmi::Error::host(Throw));
+            }
+            Self::CxaBeginCatch => {
+                let ptr = arg(0);
+                let info = ptr - EXCEPTION_INFO_SIZE;
+
+                if read_bytes(&caller, info + 12, 1)?[0] == 0 {
+                    write_bytes(&mut caller, info + 12, &[1])?;
+                    caller.data_mut().uncaught_exceptions -= 1;
+                }
+                write_bytes(&mut caller, info + 13, &[0])?;
+
+                caller.data_mut().exception_caught.push(ptr);
+                call_optional(&mut caller, "__cxa_increment_exception_refcount", &[ptr])?;
+
+                exception_ptr(&mut caller, ptr)?
+            }
+            Self::CxaEndCatch => {
+                call_optional(&mut caller, "setThrew", &[0, 0])?;
+
+                if let Some(ptr) = caller.data_mut().exception_caught.pop() {
+                    call_optional(&mut caller, "__cxa_decrement_exception_refcount", &[ptr])?;
+                }
+
+                caller.data_mut().exception_last = 0;
+                0
+            }
+            Self::CxaRethrow => {
+                let ptr = caller
+                    .data_mut()
+                    .exception_caught
+                    .pop()
+                    .ok_or_else(|| wasmi::Error::new("No exception to rethrow"))?;
+                let info = ptr - EXCEPTION_INFO_SIZE;
+
+                if read_bytes(&caller, info + 13, 1)?[0] == 0 {
+                    write_bytes(&mut caller, info + 12, &[0, 1])?;
+                    let state = caller.data_mut();
+                    state.exception_caught.push(ptr);
+                    state.uncaught_exceptions += 1;
+                }
+
+                caller.data_mut().exception_last = ptr;
+                return Err(wasmi::Error::host(Throw));
+            }
+            Self::CxaUncaughtExceptions => caller.data().uncaught_exceptions,
+            Self::FindMatchingCatch => {
+                let thrown = caller.data().exception_last;
+                if thrown == 0 {
+                    caller.data_mut().temp_ret0 = 0;
+                    return set_result(results, 0);
+                }
+
+                let info = thrown - EXCEPTION_INFO_SIZE;
+                let thrown_type = read_i32(&caller, info + 4)?;
+                if thrown_type == 0 {
+                    caller.data_mut().temp_ret0 = 0;
+                    return set_result(results, thrown);
+                }
+
+                for caught_type in params.iter().filter_map(Val::i32) {
+                    if caught_type == 0 || caught_type == thrown_type {
+                        break;
+                    }
+
+                    let args = [caught_type, thrown_type, info + 16];
+                    if call_i32(&mut caller, "__cxa_can_catch", &args)? != 0 {
+                        caller.data_mut().temp_ret0 = caught_type;
+                        return set_result(results, thrown);
+                    }
+                }
+
+                caller.data_mut().temp_ret0 = thrown_type;
+                thrown
+            }
+            Self::ResumeException => {
+                let state = caller.data_mut();
+                if state.exception_last == 0 {
+                    state.exception_last = arg(0);
+                }
+                return Err(wasmi::Error::host(Throw));
+            }
+            Self::TypeidFor => arg(0),
+            Self::SetTempRet0 => {
+                caller.data_mut().temp_ret0 = arg(0);
+                0
+            }
+            Self::GetTempRet0 => caller.data().temp_ret0,
+            Self::MemcpyBig => {
+                let (dest, src, len) = (arg(0) as usize, arg(1) as usize, arg(2) as usize);
+                let memory = memory(&caller)?;
+                let data = memory.data_mut(&mut caller);
+
+                if src + len > data.len() || dest + len > data.len() {
+                    return Err(wasmi::Error::new("memcpy out of bounds"));
+                }
+                data.copy_within(src..src + len, dest);
+                0
+            }
+            Self::ResizeHeap => {
+                let requested = arg(0) as u32 as u64;
+                let memory = memory(&caller)?;
+                let current = memory.data(&caller).len() as u64;
+                let pages = requested.saturating_sub(current).div_ceil(0x10000);
+
+                match Pages::new(pages as u32) {
+                    Some(pages) => memory.grow(&mut caller, pages).is_ok() as i32,
+                    None => 0,
+                }
+            }
+            Self::Now => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs_f64() * 1000.0)
+                    .unwrap_or_default();
+                return set_result(results, Val::F64(now.into()));
+            }
+            Self::FdWrite => {
+                let (fd, iov, iovcnt, pnum) = (arg(0), arg(1), arg(2), arg(3));
+                let mut written = 0;
+
+                for i in 0..iovcnt {
+                    let ptr = read_i32(&caller, iov + i * 8)?;
+                    let len = read_i32(&caller, iov + i * 8 + 4)?;
+
+                    if fd == 2 {
+                        let bytes = read_bytes(&caller, ptr, len as usize)?;
+                        eprint!("{}", String::from_utf8_lossy(&bytes));
+                    }
+
+                    written += len;
+                }
+
+                write_i32(&mut caller, pnum, written)?;
+                0
+            }
+            Self::SizesGet => {
+                write_i32(&mut caller, arg(0), 0)?;
+                write_i32(&mut caller, arg(1), 0)?;
+                0
+            }
+            Self::ClockTimeGet => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_nanos() as u64)
+                    .unwrap_or_default();
+                write_bytes(&mut caller, arg(2), &now.to_le_bytes())?;
+                0
+            }
+            Self::ProcExit => return Err(wasmi::Error::i32_exit(arg(0))),
+            Self::Abort => return Err(wasmi::Error::new("soljson aborted")),
+            Self::Syscall => -ENOSYS,
+            Self::Zero => 0,
+            Self::Unsupported(name) => {
+                return Err(wasmi::Error::new(format!(
+                    "Unsupported soljson import `{name}`"
+                )))
+            }
+        };
+
+        set_result(results, ret)
+    }
+}
+
+/// Call `dyn_call` export or function in table, catch C++ exceptions like
+/// emscripten `invoke_*` wrappers.
+fn invoke(
+    mut caller: Caller<'_, State>,
+    dyn_call: &str,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), wasmi::Error> {
+    let sp = call_optional(&mut caller, "stackSave", &[])?;
+
+    let (func, args) = match caller.get_export(dyn_call).and_then(Extern::into_func) {
+        Some(func) => (func, params),
+        None => {
+            let index = params.first().and_then(Val::i32).unwrap_or_default();
+            let func = caller
+                .get_export("__indirect_function_table")
+                .and_then(Extern::into_table)
+                .and_then(|t| t.get(&caller, index as u32))
+                .and_then(|v| v.funcref().and_then(|f| f.func()).copied())
+                .ok_or_else(|| wasmi::Error::new(format!("Invalid function index {index}")))?;
+            (func, &params[1..])
+        }
+    };
+
+    match func.call(&mut caller, args, results) {
+        Ok(()) => Ok(()),
+        Err(e) if e.downcast_ref::<Throw>().is_some() => {
+            if let Some(sp) = sp {
+                call_optional(&mut caller, "stackRestore", &[sp])?;
+            }
+            call_optional(&mut caller, "setThrew", &[1, 0])?;
+
+            for (r, ty) in results.iter_mut().zip(func.ty(&caller).results()) {
+                *r = Val::default(*ty);
+            }
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Pointer to thrown object passed to catch clause.
+fn exception_ptr(caller: &mut Caller<'_, State>, ptr: i32) -> Result<i32, wasmi::Error> {
+    let info = ptr - EXCEPTION_INFO_SIZE;
+    let ty = read_i32(caller, info + 4)?;
+
+    if call_optional(caller, "__cxa_is_pointer_type", &[ty])?.unwrap_or_default() != 0 {
+        return read_i32(caller, ptr);
+    }
+
+    match read_i32(caller, info + 16)? {
+        0 => Ok(ptr),
+        adjusted => Ok(adjusted),
+    }
+}
+
+fn set_result(results: &mut [Val], value: impl Into<Val>) -> Result<(), wasmi::Error> {
+    if let Some(r) = results.first_mut() {
+        *r = value.into();
+    }
+    Ok(())
+}
+
+fn memory(caller: &Caller<'_, State>) -> Result<Memory, wasmi::Error> {
+    caller
+        .data()
+        .memory
+        .or_else(|| caller.get_export("memory").and_then(Extern::into_memory))
+        .ok_or_else(|| wasmi::Error::new("No memory in soljson"))
+}
+
+fn read_bytes(caller: &Caller<'_, State>, ptr: i32, len: usize) -> Result<Vec<u8>, wasmi::Error> {
+    let data = memory(caller)?.data(caller);
+    let ptr = ptr as u32 as usize;
+
+    data.get(ptr..ptr + len)
+        .map(|b| b.to_vec())
+        .ok_or_else(|| wasmi::Error::new("Memory access out of bounds"))
+}
+
+fn write_bytes(caller: &mut Caller<'_, State>, ptr: i32, bytes: &[u8]) -> Result<(), wasmi::Error> {
+    let memory = memory(caller)?;
+    memory
+        .write(caller, ptr as u32 as usize, bytes)
+        .map_err(|e| wasmi::Error::new(e.to_string()))
+}
+
+fn read_i32(caller: &Caller<'_, State>, ptr: i32) -> Result<i32, wasmi::Error> {
+    let bytes = read_bytes(caller, ptr, 4)?;
+    Ok(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn write_i32(caller: &mut Caller<'_, State>, ptr: i32, value: i32) -> Result<(), wasmi::Error> {
+    write_bytes(caller, ptr, &value.to_le_bytes())
+}
+
+/// Call export `name` if present, return its first result.
+fn call_optional(
+    caller: &mut Caller<'_, State>,
+    name: &str,
+    args: &[i32],
+) -> Result<Option<i32>, wasmi::Error> {
+    match caller.get_export(name).and_then(Extern::into_func) {
+        Some(func) => call_func(caller, func, args).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn call_i32(caller: &mut Caller<'_, State>, name: &str, args: &[i32]) -> Result<i32, wasmi::Error> {
+    call_optional(caller, name, args)?
+        .ok_or_else(|| wasmi::Error::new(format!("Missing soljson export `{name}`")))
+}
+
+fn call_func(
+    mut ctx: impl wasmi::AsContextMut,
+    func: Func,
+    args: &[i32],
+) -> Result<i32, wasmi::Error> {
+    let ty = func.ty(&ctx);
+
+    // Missing trailing arguments are passed as zero.
+    let params: Vec<Val> = ty
+        .params()
+        .iter()
+        .enumerate()
+        .map(|(i, t)| match args.get(i) {
+            Some(v) => Val::I32(*v),
+            None => Val::default(*t),
+        })
+        .collect();
+    let mut results: Vec<Val> = ty.results().iter().map(|t| Val::default(*t)).collect();
+
+    func.call(&mut ctx, &params, &mut results)?;
+
+    Ok(results.first().and_then(Val::i32).unwrap_or_default())
+}
+
+/// Extract wasm module embedded in `soljson.js`.
+pub fn extract_wasm(soljson: &str) -> Result<Vec<u8>> {
+    let start = soljson
+        .find(WASM_DATA_PREFIX)
+        .ok_or(anyhow!("No embedded wasm in soljson"))?
+        + WASM_DATA_PREFIX.len();
+
+    let data = &soljson[start..];
+    let end = data
+        .find(['"', '\''])
+        .ok_or(anyhow!("Unterminated wasm data in soljson"))?;
+
+    Ok(base64::engine::general_purpose::STANDARD.decode(&data[..end])?)
+}
+
+/// Compile standard json `input` with solc in `soljson.js` at `path`.
+pub fn compile(path: &Path, input: &str) -> Result<String> {
+    let soljson = fs::read_to_string(path)?;
+    compile_wasm(&extract_wasm(&soljson)?, input)
+}
+
+fn compile_wasm(wasm: &[u8], input: &str) -> Result<String> {
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm)?;
+    let mut store = Store::new(&engine, State::default());
+    let mut linker = Linker::<State>::new(&engine);
+
+    for import in module.imports() {
+        let (module_name, name) = (import.module(), import.name());
+
+        match import.ty() {
+            ExternType::Func(ty) => {
+                let host = Import::from_name(name);
+                linker.func_new(
+                    module_name,
+                    name,
+                    ty.clone(),
+                    move |caller, params, results| host.call(caller, params, results),
+                )?;
+            }
+            ExternType::Memory(ty) => {
+                let memory = Memory::new(&mut store, *ty).map_err(|e| anyhow!("{e}"))?;
+                store.data_mut().memory = Some(memory);
+                linker.define(module_name, name, memory)?;
+            }
+            ExternType::Table(ty) => {
+                let table = Table::new(&mut store, *ty, Val::default(ty.element()))
+                    .map_err(|e| anyhow!("{e}"))?;
+                linker.define(module_name, name, table)?;
+            }
+            ExternType::Global(ty) => {
+                let global = Global::new(&mut store, Val::default(ty.content()), ty.mutability());
+                linker.define(module_name, name, global)?;
+            }
+        }
+    }
+
+    let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+
+    if store.data().memory.is_none() {
+        store.data_mut().memory = instance.get_memory(&store, "memory");
+    }
+    let memory = store.data().memory.ok_or(anyhow!("No memory in soljson"))?;
+
+    let export = |store: &Store<State>, name: &str| instance.get_func(store, name);
+
+    for name in ["emscripten_stack_init", "_initialize", "__wasm_call_ctors"] {
+        if let Some(func) = export(&store, name) {
+            call_func(&mut store, func, &[])?;
+        }
+    }
+
+    let alloc = export(&store, "solidity_alloc")
+        .or_else(|| export(&store, "malloc"))
+        .ok_or(anyhow!("No allocator in soljson"))?;
+    let solidity_compile =
+        export(&store, "solidity_compile").ok_or(anyhow!("No `solidity_compile` in soljson"))?;
+
+    let mut data = input.as_bytes().to_vec();
+    data.push(0);
+
+    let ptr = call_func(&mut store, alloc, &[data.len() as i32])?;
+    memory
+        .write(&mut store, ptr as u32 as usize, &data)
+        .map_err(|e| anyhow!("{e}"))?;
+
+    let out = call_func(&mut store, solidity_compile, &[ptr, 0, 0])? as u32 as usize;
+
+    let mem = memory.data(&store);
+    let len = mem[out..]
+        .iter()
+        .position(|b| *b == 0)
+        .ok_or(anyhow!("Unterminated output of soljson"))?;
+    let output = String::from_utf8(mem[out..out + len].to_vec())?;
+
+    if let Some(reset) = export(&store, "solidity_reset") {
+        call_func(&mut store, reset, &[])?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod test {
+    use base64::Engine as _;
+
+    use super::{compile_wasm, extract_wasm};
+
+    /// Calls function throwing C++ exception through `invoke_vi`, return
+    /// json telling if the exception is caught.
+    const MODULE: &str = r#"
+        (module
+          (import "env" "invoke_vi" (func $invoke_vi (param i32 i32)))
+          (import "env" "__cxa_throw" (func $throw (param i32 i32 i32)))
+          (memory (export "memory") 2)
+          (table (export "__indirect_function_table") 2 funcref)
+          (elem (i32.const 1) $thrower)
+          (global $threw (mut i32) (i32.const 0))
+          (global $sp (mut i32) (i32.const 4096))
+          (data (i32.const 1024) "{\"threw\":false}\00")
+          (data (i32.const 1040) "{\"threw\":true}\00")
+          (func $thrower (param i32)
+            (call $throw (i32.const 2048) (i32.const 0) (i32.const 0)))
+          (func (export "setThrew") (param i32 i32)
+            (global.set $threw (local.get 0)))
+          (func (export "stackSave") (result i32) (global.get $sp))
+          (func (export "stackRestore") (param i32) (global.set $sp (local.get 0)))
+          (func (export "solidity_alloc") (param i32) (result i32) (i32.const 8192))
+          (func (export "solidity_compile") (param i32 i32 i32) (result i32)
+            (call $invoke_vi (i32.const 1) (i32.const 0))
+            (if (result i32) (global.get $threw)
+              (then (i32.const 1040))
+              (else (i32.const 1024)))))
+    "#;
+
+    #[test]
+    fn test_compile_wasm() {
+        let wasm = wat::parse_str(MODULE).unwrap();
+
+        let soljson = format!(
+            "var Module; var wasmBinaryFile = \"data:application/octet-stream;base64,{}\";",
+            base64::engine::general_purpose::STANDARD.encode(&wasm)
+        );
+        let extracted = extract_wasm(&soljson).unwrap();
+        assert_eq!(extracted, wasm);
+
+        let output = compile_wasm(&extracted, "{}").unwrap();
+        assert_eq!(output, "{\"threw\":true}");
+    }
+
+    const SOLJSON_URL: &str =
+        "https://binaries.soliditylang.org/wasm/soljson-v0.8.20+commit.a1b79de6.js";
+
+    /// Compile with a real soljson, read from `SNAPPER_SOLJSON` or downloaded
+    /// from solc-bin.
+    #[test]
+    #[ignore = "needs network or `SNAPPER_SOLJSON`"]
+    fn test_soljson() {
+        let soljson = match std::env::var("SNAPPER_SOLJSON") {
+            Ok(path) => std::fs::read_to_string(path).unwrap(),
+            Err(_) => attohttpc::get(SOLJSON_URL).send().unwrap().text().unwrap(),
+        };
+        let wasm = extract_wasm(&soljson).unwrap();
+
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": {
+                "A.sol": {
+                    "content": "pragma solidity ^0.8.0;\n\
+                                contract A { function f() public pure returns (uint) { return 1; } }\n"
+                }
+            },
+            "settings": {
+                "outputSelection": { "*": { "*": ["abi", "evm.bytecode.object"] } }
+            }
+        });
+
+        let output = compile_wasm(&wasm, &input.to_string()).unwrap();
+        let output: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        let bytecode = &output["contracts"]["A.sol"]["A"]["evm"]["bytecode"]["object"];
+        assert!(!bytecode.as_str().unwrap().is_empty(), "{output}");
+        assert_eq!(output["contracts"]["A.sol"]["A"]["abi"][0]["name"], "f");
+
+        // Compile errors are reported in output, not thrown.
+        let input = input.to_string().replace("return 1;", "return x;");
+        let output = compile_wasm(&wasm, &input).unwrap();
+        let output: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(output["errors"][0]["severity"], "error", "{output}");
+    }
+}
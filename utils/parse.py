@@ -1,13 +1,26 @@
+# Usage: parse.py <version_file> <upstream_list> <upstream_dir> [platform] [min_version]
+#
+# `upstream_dir` is directory in solc-bin, like `linux-amd64`, `macosx-amd64`
+# or `wasm`. `platform` is key used in registry, default to `upstream_dir`.
+# Use it when names differ, like `macosx-amd64` builds for `macos-arm64`
+# (universal binaries), or `linux-arm64`, `wasm`. Builds older than
+# `min_version` are skipped.
+
 import requests
 import sys
 import json
 
 github_prefix = "https://raw.githubusercontent.com/ethereum/solc-bin/gh-pages/%s/%s"
 
+def parse_version(version):
+    return tuple(int(n) for n in version.split("."))
+
 def main():
     verison_file = sys.argv[1]
     upstream_file = sys.argv[2]
-    platform = sys.argv[3]
+    upstream_dir = sys.argv[3]
+    platform = sys.argv[4] if len(sys.argv) > 4 else upstream_dir
+    min_version = parse_version(sys.argv[5]) if len(sys.argv) > 5 else None
 
     versions = json.load(open(verison_file))
 
@@ -17,12 +30,15 @@ def main():
 
     for build in builds["builds"]:
         version = build["version"]
+        if min_version and parse_version(version) < min_version:
+            continue
+
         keccak256 = build["keccak256"]
         sha256 = build["sha256"]
         path = build["path"]
 
         art = {
-            "urls": [ github_prefix % (platform, path) ],
+            "urls": [ github_prefix % (upstream_dir, path) ],
             "keccak256": keccak256,
             "sha256": sha256,
         }
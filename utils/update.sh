@@ -0,0 +1,19 @@
+#!/bin/sh
+# Regenerate `solidity.json` with builds of all platforms from solc-bin.
+#
+# Usage: update.sh
+
+set -e
+
+cd "$(dirname "$0")"
+
+list="https://raw.githubusercontent.com/ethereum/solc-bin/gh-pages/%s/list.json"
+
+for dir in linux-amd64 linux-arm64 windows-amd64 wasm; do
+    python3 parse.py solidity.json "$(printf "$list" "$dir")" "$dir"
+done
+
+python3 parse.py solidity.json "$(printf "$list" macosx-amd64)" macosx-amd64 macos-amd64
+
+# Builds are universal binaries since 0.8.24, older ones are amd64 only.
+python3 parse.py solidity.json "$(printf "$list" macosx-amd64)" macosx-amd64 macos-arm64 0.8.24